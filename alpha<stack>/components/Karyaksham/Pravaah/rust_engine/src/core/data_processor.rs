@@ -1,35 +1,175 @@
 use crate::core::file_handler::{FileFormat, ObjectStorageFileHandler};
 use crate::utils::error::{KaryakshamError, Result};
+use crate::{Aggregation, CsvProcessingParams, FilterCondition, FilterValue, Transformation};
+use anyhow::Context;
 use arrow::{
+    array::{Array, ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray, TimestampMillisecondArray},
     csv::Reader as ArrowCsvReader,
     compute,
+    datatypes::{DataType, Field, Schema, SchemaRef},
     record_batch::RecordBatch,
 };
+use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
 use csv::{ReaderBuilder, WriterBuilder};
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::{self, BoxStream};
 use futures::{StreamExt, SinkExt}; // Needed for AsyncArrowWriter
 use parquet::{
-    basic::{Compression},
+    arrow::ProjectionMask,
+    basic::{BrotliLevel, Compression, ZstdLevel},
+    file::{metadata::RowGroupMetaData, statistics::Statistics},
+    schema::types::ColumnPath,
 };
-use parquet_arrow::AsyncArrowWriter;
+use parquet_arrow::{AsyncArrowWriter, ParquetRecordBatchStreamBuilder};
+use pyo3::types::PyDict;
+use pyo3::{PyObject, Python};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::io::Cursor;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
 use tokio_util::compat::TokioAsyncReadCompatExt; // For .compat()
 
+/// Default number of dataset files read and filtered concurrently when
+/// `ProcessingJobParams::Dataset::max_concurrent_files` isn't set.
+const DEFAULT_DATASET_CONCURRENCY: usize = 4;
+
+/// A single comparison operator usable in a `Predicate::Compare` leaf.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ComparisonOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A literal value compared against a column in a `Predicate`. Each variant is cast to the
+/// target column's Arrow `DataType` before comparison (see `literal_to_array`), so e.g. an `Int`
+/// literal compared against an `Int32` column is cast down rather than rejected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PredicateLiteral {
+    String(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    /// Milliseconds since the Unix epoch.
+    Timestamp(i64),
+}
+
+/// A composable filter expression evaluated against Arrow `RecordBatch` columns. Leaves compile
+/// to an Arrow `compute` comparison kernel producing a `BooleanArray` mask; combinators merge
+/// their children's masks with `compute::and`/`compute::or`/`compute::not` (see
+/// `compile_predicate`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Predicate {
+    Compare {
+        column: String,
+        op: ComparisonOp,
+        literal: PredicateLiteral,
+    },
+    In {
+        column: String,
+        values: Vec<PredicateLiteral>,
+    },
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+    Not(Box<Predicate>),
+}
+
+/// The compression codec used when writing a Parquet output. Mirrors `parquet::basic::Compression`
+/// but stays serde-friendly (the real type isn't `Serialize`/`Deserialize`) and keeps codec-level
+/// tuning knobs, like ZSTD's level, alongside the codec that needs them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ParquetCompression {
+    Uncompressed,
+    Snappy,
+    Gzip,
+    Zstd { level: i32 },
+    Brotli { level: u32 },
+    Lz4,
+}
+
+/// Tuning knobs for a Parquet output, threaded into `WriterProperties::builder()` before a
+/// `AsyncArrowWriter` is constructed (see `build_writer_properties`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParquetWriteOptions {
+    pub compression: ParquetCompression,
+    /// Maximum number of rows buffered per row group before it's flushed; `None` keeps the
+    /// writer's default.
+    pub row_group_size: Option<usize>,
+    /// Target byte size for an individual data page; `None` keeps the writer's default.
+    pub data_page_size_limit: Option<usize>,
+    pub dictionary_enabled: bool,
+    /// Columns to write a bloom filter for, so a later `Filter` job reading this file back can
+    /// prune row groups via `prune_row_groups`'s bloom-filter check.
+    pub bloom_filter_columns: Vec<String>,
+    /// Caps how much encoded Parquet data `BoundedParquetWriter` buffers in memory before
+    /// flushing to the output sink, as a human-readable size like `"64MB"` (see
+    /// `parse_byte_size`). `None` leaves the Arrow writer's own buffering behavior untouched.
+    pub write_buffer_size: Option<String>,
+}
+
+impl Default for ParquetWriteOptions {
+    fn default() -> Self {
+        ParquetWriteOptions {
+            compression: ParquetCompression::Snappy,
+            row_group_size: None,
+            data_page_size_limit: None,
+            dictionary_enabled: true,
+            bloom_filter_columns: Vec::new(),
+            write_buffer_size: None,
+        }
+    }
+}
+
 /// Defines the parameters for various data processing jobs.
 /// This enum will be serialized/deserialized when passed from Python.
 #[derive(Debug, Serialize, Deserialize)]
 pub enum ProcessingJobParams {
-    /// Parameters for filtering a CSV file based on a column's value.
-    CsvFilter {
-        filter_column: String,
-        filter_value: String,
+    /// Parameters for filtering rows with a composable predicate expression, evaluated against
+    /// the input's Arrow schema so comparisons honor each column's actual data type.
+    Filter {
+        expr: Predicate,
         output_format: FileFormat, // The desired output format (e.g., CSV, Parquet)
+        /// Column names to keep, in output order; `None` keeps every column. Applied to the
+        /// output batch only, after `expr` is evaluated against the full input schema — `expr`
+        /// may reference a column this excludes, so it can't be pushed down to the read side.
+        columns: Option<Vec<String>>,
+        /// Writer tuning used when `output_format` is `FileFormat::Parquet`; ignored otherwise.
+        parquet_options: ParquetWriteOptions,
     },
     /// Parameters for converting a CSV file to Parquet format.
-    CsvToParquet,
+    CsvToParquet { parquet_options: ParquetWriteOptions },
+    /// Parameters for filtering an entire Hive-partitioned dataset — a prefix/directory of many
+    /// CSV/Parquet files treated as one logical table (see `DataProcessor::process_dataset`).
+    /// `input_path` is the dataset's root prefix rather than a single file's path.
+    Dataset {
+        expr: Predicate,
+        output_format: FileFormat,
+        /// Column names to keep, in output order; may include Hive partition columns. `None`
+        /// keeps every column (data columns followed by partition columns).
+        columns: Option<Vec<String>>,
+        parquet_options: ParquetWriteOptions,
+        /// Bounds how many dataset files are read and filtered concurrently; `None` defaults to
+        /// `DEFAULT_DATASET_CONCURRENCY`.
+        max_concurrent_files: Option<usize>,
+    },
+    /// Rewrites an existing Parquet file with new physical properties (compression, row-group
+    /// sizing, column set, bloom filters) without a lossy CSV round-trip (see
+    /// `DataProcessor::process_parquet_rewrite`).
+    ParquetRewrite {
+        parquet_options: ParquetWriteOptions,
+        /// Column names to keep, in output order; `None` keeps every column.
+        columns: Option<Vec<String>>,
+        /// Target row count per output row group; batches read from the input are coalesced or
+        /// split to this size before being written. `None` writes batches through as read,
+        /// leaving row-group sizing to `parquet_options.row_group_size`/the writer's default.
+        target_rows_per_group: Option<usize>,
+    },
     // Add other job types as needed, e.g., ColumnAggregation, DataJoin, etc.
 }
 
@@ -60,223 +200,206 @@ impl DataProcessor {
         );
 
         match params {
-            ProcessingJobParams::CsvFilter {
-                filter_column,
-                filter_value,
-                output_format,
-            } => {
-                self.process_csv_filter(
+            ProcessingJobParams::Filter { expr, output_format, columns, parquet_options } => {
+                self.process_filter(
                     input_path,
                     output_path,
-                    &filter_column,
-                    &filter_value,
+                    &expr,
                     output_format,
+                    columns.as_deref(),
+                    &parquet_options,
                 )
                 .await
             }
-            ProcessingJobParams::CsvToParquet => {
-                self.process_csv_to_parquet(input_path, output_path).await
+            ProcessingJobParams::CsvToParquet { parquet_options } => {
+                self.process_csv_to_parquet(input_path, output_path, &parquet_options).await
+            }
+            ProcessingJobParams::Dataset { expr, output_format, columns, parquet_options, max_concurrent_files } => {
+                self.process_dataset(
+                    input_path,
+                    output_path,
+                    &expr,
+                    output_format,
+                    columns.as_deref(),
+                    &parquet_options,
+                    max_concurrent_files.unwrap_or(DEFAULT_DATASET_CONCURRENCY),
+                )
+                .await
+            }
+            ProcessingJobParams::ParquetRewrite { parquet_options, columns, target_rows_per_group } => {
+                self.process_parquet_rewrite(
+                    input_path,
+                    output_path,
+                    columns.as_deref(),
+                    &parquet_options,
+                    target_rows_per_group,
+                )
+                .await
             }
         }
     }
 
-    /// Processes a CSV file by filtering rows based on a column's value.
-    /// Supports outputting to CSV or Parquet format.
-    ///
-    /// This method attempts to read CSV data in chunks, parallelize the filtering
-    /// of these in-memory chunks using Rayon for CPU-bound tasks, and then write
-    /// the filtered data to the specified output format.
-    async fn process_csv_filter(
+    /// Filters rows against a composable `Predicate` expression and writes the surviving rows to
+    /// the specified output format. The input format is inferred from `input_path`'s extension
+    /// (see `infer_file_format`): CSV is parsed through Arrow's CSV reader batch by batch, while
+    /// Parquet is opened via its footer metadata first so whole row groups that cannot satisfy
+    /// `expr` are skipped before any decoding happens (see `prune_row_groups`). Either way, the
+    /// compiled boolean mask is applied with `compute::filter_record_batch` one batch at a time.
+    /// A Parquet input is read fully into memory before its footer is parsed, and a CSV output
+    /// is buffered in memory until every batch has been written.
+    async fn process_filter(
         &self,
         input_path: &str,
         output_path: &str,
-        filter_column: &str,
-        filter_value: &str,
+        expr: &Predicate,
         output_format: FileFormat,
+        columns: Option<&[String]>,
+        parquet_options: &ParquetWriteOptions,
     ) -> Result<()> {
         log::info!(
-            "Processing CSV filter: column='{}', value='{}', output_format='{:?}'",
-            filter_column,
-            filter_value,
-            output_format
+            "Processing predicate filter: expr='{:?}', output_format='{:?}', columns='{:?}'",
+            expr,
+            output_format,
+            columns
         );
 
-        let input_stream = self.file_handler.read_file(input_path).await?;
-        let mut reader = BufReader::new(input_stream).lines();
-
-        // Read headers line
-        let headers_line = reader
-            .next_line()
-            .await?
-            .ok_or_else(|| KaryakshamError::ProcessingError("CSV file is empty, no headers found.".to_string()))?;
-
-        // Parse headers using csv crate
-        let headers: Vec<String> = ReaderBuilder::new()
-            .has_headers(false) // We already read the line
-            .from_reader(Cursor::new(headers_line.as_bytes()))
-            .headers()
-            .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to parse CSV headers: {}", e)))?
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
+        let (schema, mut batches): (SchemaRef, BoxStream<'_, Result<RecordBatch>>) =
+            match infer_file_format(input_path) {
+                FileFormat::Csv => {
+                    let input_stream = self.file_handler.read_file(input_path).await?;
+                    let arrow_csv_reader = ArrowCsvReader::Builder::new()
+                        .has_headers(true)
+                        .build(BufReader::new(input_stream).compat())
+                        .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to create Arrow CSV reader: {}", e)))?;
 
-        let filter_col_index = headers
-            .iter()
-            .position(|h| h == filter_column)
-            .ok_or_else(|| {
-                KaryakshamError::ProcessingError(format!(
-                    "Filter column '{}' not found in CSV headers",
-                    filter_column
-                ))
-            })?;
+                    let full_schema = arrow_csv_reader.schema();
+                    let batches = stream::iter(arrow_csv_reader)
+                        .map(|r| {
+                            r.map_err(|e| {
+                                KaryakshamError::ProcessingError(format!("Failed to read Arrow record batch from CSV: {}", e))
+                            })
+                        })
+                        .boxed();
+
+                    // `columns` is an *output* projection, not applied here: `expr` may reference
+                    // a column excluded from it, and `compile_predicate` needs that column present
+                    // to evaluate against. Filter against the full schema/batch below and project
+                    // the output afterward instead (mirrors `process_dataset`'s pattern).
+                    (full_schema, batches)
+                }
+                FileFormat::Parquet => {
+                    let mut input_stream = self.file_handler.read_file(input_path).await?;
+                    let mut raw = Vec::new();
+                    input_stream.read_to_end(&mut raw).await?;
+
+                    let mut builder = ParquetRecordBatchStreamBuilder::new(Bytes::from(raw))
+                        .await
+                        .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to read Parquet footer metadata: {}", e)))?;
+
+                    let full_schema = builder.schema().clone();
+                    let total_row_groups = builder.metadata().row_groups().len();
+                    let surviving_row_groups = prune_row_groups(&mut builder, &full_schema, expr).await?;
+                    log::info!(
+                        "Parquet row-group pruning for '{}': reading {}/{} row groups",
+                        input_path,
+                        surviving_row_groups.len(),
+                        total_row_groups
+                    );
+
+                    // `columns` is an output projection, applied after filtering below (see the
+                    // write loop) rather than pushed down via `ProjectionMask` here: `expr` may
+                    // reference a column excluded from it, which `compile_predicate` needs present
+                    // to evaluate against.
+                    let batches = builder
+                        .with_row_groups(surviving_row_groups)
+                        .build()
+                        .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to build pruned Parquet batch stream: {}", e)))?
+                        .map(|r| {
+                            r.map_err(|e| {
+                                KaryakshamError::ProcessingError(format!("Failed to read Arrow record batch from Parquet: {}", e))
+                            })
+                        })
+                        .boxed();
+                    (full_schema, batches)
+                }
+                other => {
+                    return Err(KaryakshamError::NotImplemented(format!(
+                        "Input format {:?} not supported for predicate filter.",
+                        other
+                    )))
+                }
+            };
 
         let output_sink = self.file_handler.create_file(output_path).await?;
 
         match output_format {
             FileFormat::Csv => {
-                let mut csv_writer = WriterBuilder::new()
-                    .has_headers(false) // We'll write headers explicitly
-                    .from_writer(output_sink.compat());
-
-                // Write headers to output CSV
-                csv_writer
-                    .write_record(&headers)
-                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to write headers to output CSV: {}", e)))?;
-                csv_writer
-                    .flush()
-                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to flush headers: {}", e)))?;
-
-                const BATCH_SIZE: usize = 10_000; // Process records in batches for parallelism
-                let mut record_batch: Vec<csv::StringRecord> = Vec::with_capacity(BATCH_SIZE);
-
-                while let Some(line_result) = reader.next_line().await {
-                    let line = line_result?
-                        .ok_or_else(|| KaryakshamError::ProcessingError("Unexpected end of CSV file during line read".to_string()))?;
-
-                    // Parse the line into a single StringRecord
-                    let record = ReaderBuilder::new()
-                        .has_headers(false)
-                        .from_reader(Cursor::new(line.as_bytes()))
-                        .records()
-                        .next() // Get the single record from this line
-                        .ok_or_else(|| KaryakshamError::ProcessingError(format!("Failed to parse CSV record from line: {}", line)))??; // Handle Option and Result
-
-                    record_batch.push(record);
-
-                    if record_batch.len() >= BATCH_SIZE {
-                        // Process this batch in parallel using Rayon
-                        let filtered_batch: Vec<csv::StringRecord> = record_batch
-                            .par_iter()
-                            .filter(|record| {
-                                record
-                                    .get(filter_col_index)
-                                    .map_or(false, |col_val| col_val == filter_value)
-                            })
-                            .cloned() // Clone to move ownership out of the parallel iterator
-                            .collect();
-
-                        // Write filtered batch (sequential write to the async stream)
-                        for record in filtered_batch {
-                            csv_writer
-                                .write_record(&record)
-                                .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to write record to output CSV: {}", e)))?;
-                        }
-                        csv_writer
-                            .flush()
-                            .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to flush CSV writer: {}", e)))?;
+                // `arrow::csv::Writer` only writes to a synchronous `std::io::Write`, so batches
+                // are serialized into an in-memory buffer and flushed to the async sink once at
+                // the end.
+                let mut buffer: Vec<u8> = Vec::new();
+                {
+                    let mut csv_writer = WriterBuilder::new().has_headers(true).build(&mut buffer);
 
-                        record_batch.clear(); // Clear for the next batch
-                    }
-                }
-
-                // Process any remaining records in the last batch
-                if !record_batch.is_empty() {
-                    let filtered_batch: Vec<csv::StringRecord> = record_batch
-                        .par_iter()
-                        .filter(|record| {
-                            record
-                                .get(filter_col_index)
-                                .map_or(false, |col_val| col_val == filter_value)
-                        })
-                        .cloned()
-                        .collect();
+                    while let Some(batch_result) = batches.next().await {
+                        let batch = batch_result?;
+                        let mask = compile_predicate(expr, &batch, &schema)?;
+                        let filtered_batch = compute::filter_record_batch(&batch, &mask)
+                            .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to filter Arrow record batch: {}", e)))?;
+                        let output_batch = project_batch(&filtered_batch, &schema, columns)?;
 
-                    for record in filtered_batch {
                         csv_writer
-                            .write_record(&record)
-                            .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to write record to output CSV: {}", e)))?;
+                            .write(&output_batch)
+                            .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to write filtered batch to CSV: {}", e)))?;
                     }
-                    csv_writer
-                        .flush()
-                        .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to flush CSV writer: {}", e)))?;
                 }
+
+                let mut output_sink = output_sink;
+                output_sink.write_all(&buffer).await?;
+                output_sink.flush().await?;
             }
             FileFormat::Parquet => {
-                // For Parquet output, it's more efficient to use Arrow's CSV reader
-                // to parse into RecordBatches, then filter these batches, and finally
-                // write the filtered batches to Parquet.
-                let mut arrow_csv_reader = ArrowCsvReader::Builder::new()
-                    .has_headers(true)
-                    .build(BufReader::new(
-                        self.file_handler.read_file(input_path).await?,
-                    ).compat()) // Re-read the stream for Arrow-CSV parser
-                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to create Arrow CSV reader: {}", e)))?;
-
-                let schema = arrow_csv_reader.schema();
-                let filter_col_idx = schema
-                    .index_of(filter_column)
-                    .ok_or_else(|| {
-                        KaryakshamError::ProcessingError(format!(
-                            "Filter column '{}' not found in Arrow schema for Parquet output",
-                            filter_column
-                        ))
-                    })?;
-
-                let props = Some(Arc::new(
-                    parquet::file::writer::Properties::builder()
-                        .set_compression(Compression::SNAPPY)
-                        .build(),
-                ));
-
-                let mut arrow_parquet_writer = AsyncArrowWriter::try_new(
+                let output_schema = match columns {
+                    Some(columns) => {
+                        let indices = projection_indices(&schema, columns)?;
+                        Arc::new(schema.project(&indices).map_err(|e| {
+                            KaryakshamError::ProcessingError(format!("Failed to project output Parquet schema: {}", e))
+                        })?)
+                    }
+                    None => schema.clone(),
+                };
+                let props = Some(Arc::new(build_writer_properties(parquet_options, &output_schema)?));
+                let max_buffer_bytes = parse_byte_size(parquet_options.write_buffer_size.as_deref())?;
+
+                let arrow_parquet_writer = AsyncArrowWriter::try_new(
                     output_sink,
-                    schema.clone(),
+                    output_schema.clone(),
                     props,
                 )
                 .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to create Parquet Arrow writer: {}", e)))?;
+                let mut bounded_writer = BoundedParquetWriter::new(arrow_parquet_writer, max_buffer_bytes);
 
-                while let Some(batch_result) = arrow_csv_reader.next() {
-                    let batch = batch_result.map_err(|e| KaryakshamError::ProcessingError(format!("Failed to read Arrow record batch from CSV: {}", e)))?;
+                while let Some(batch_result) = batches.next().await {
+                    let batch = batch_result?;
 
-                    // Apply filter using Arrow compute functions
-                    let filter_array = compute::eq(
-                        batch.column(filter_col_idx),
-                        &arrow::array::StringArray::from(vec![filter_value]),
-                    )
-                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to apply Arrow filter: {}", e)))?;
-
-                    let filtered_batch = compute::filter_record_batch(&batch, &filter_array.into())
+                    let mask = compile_predicate(expr, &batch, &schema)?;
+                    let filtered_batch = compute::filter_record_batch(&batch, &mask)
                         .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to filter Arrow record batch: {}", e)))?;
+                    let output_batch = project_batch(&filtered_batch, &schema, columns)?;
 
-                    arrow_parquet_writer
-                        .write(&filtered_batch)
-                        .await
-                        .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to write filtered Arrow batch to Parquet: {}", e)))?;
+                    bounded_writer.write(&output_batch).await?;
                 }
 
-                arrow_parquet_writer
-                    .close()
-                    .await
-                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to close Parquet writer: {}", e)))?;
+                bounded_writer.close().await?;
             }
             _ => {
                 return Err(KaryakshamError::NotImplemented(format!(
-                    "Output format {:?} not supported for CSV filter.",
+                    "Output format {:?} not supported for predicate filter.",
                     output_format
                 )))
             }
         }
-        log::info!("CSV filter processing complete for '{}'", input_path);
+        log::info!("Predicate filter processing complete for '{}'", input_path);
         Ok(())
     }
 
@@ -285,7 +408,12 @@ impl DataProcessor {
     /// This method uses the `arrow-csv` crate to read the CSV data into Arrow
     /// RecordBatches, and then the `parquet-arrow` crate to efficiently write
     /// these RecordBatches to a Parquet file, leveraging asynchronous I/O.
-    async fn process_csv_to_parquet(&self, input_path: &str, output_path: &str) -> Result<()> {
+    async fn process_csv_to_parquet(
+        &self,
+        input_path: &str,
+        output_path: &str,
+        parquet_options: &ParquetWriteOptions,
+    ) -> Result<()> {
         log::info!(
             "Converting CSV to Parquet: input='{}', output='{}'",
             input_path,
@@ -302,37 +430,1712 @@ impl DataProcessor {
 
         let output_sink = self.file_handler.create_file(output_path).await?;
 
-        // Parquet writer properties (e.g., compression)
-        let props = Some(Arc::new(
-            parquet::file::writer::Properties::builder()
-                .set_compression(Compression::SNAPPY) // SNAPPY is a good default for performance
-                .build(),
-        ));
+        // Parquet writer properties (compression, row-group/page sizing, bloom filters, etc.)
+        let props = Some(Arc::new(build_writer_properties(parquet_options, &csv_reader.schema())?));
+        let max_buffer_bytes = parse_byte_size(parquet_options.write_buffer_size.as_deref())?;
 
-        // Create an Arrow Parquet writer
-        let mut arrow_parquet_writer = AsyncArrowWriter::try_new(
+        // Create an Arrow Parquet writer, wrapped so its buffered bytes are flushed to the async
+        // sink once they exceed `max_buffer_bytes`, keeping memory use bounded regardless of input size.
+        let arrow_parquet_writer = AsyncArrowWriter::try_new(
             output_sink,
             csv_reader.schema(),
             props,
         )
         .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to create Parquet Arrow writer: {}", e)))?;
+        let mut bounded_writer = BoundedParquetWriter::new(arrow_parquet_writer, max_buffer_bytes);
 
         // Read batches from CSV and write them to Parquet
         while let Some(batch_result) = csv_reader.next() {
             let batch = batch_result.map_err(|e| KaryakshamError::ProcessingError(format!("Failed to read Arrow record batch from CSV: {}", e)))?;
-            arrow_parquet_writer
-                .write(&batch)
-                .await
-                .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to write Arrow batch to Parquet: {}", e)))?;
+            bounded_writer.write(&batch).await?;
         }
 
         // Finalize and close the Parquet writer
-        arrow_parquet_writer
+        bounded_writer.close().await?;
+
+        log::info!("CSV to Parquet conversion complete for '{}'", input_path);
+        Ok(())
+    }
+
+    /// Filters an entire Hive-partitioned dataset — every CSV/Parquet object under the
+    /// `input_path` prefix — as a single logical table, writing the combined, filtered result to
+    /// `output_path`.
+    ///
+    /// Lists objects under the prefix, infers each file's Arrow schema and validates they agree
+    /// (see `merge_dataset_schema`), and parses `key=value` path segments into virtual partition
+    /// columns appended to every batch (see `parse_hive_partitions`). A file is skipped entirely,
+    /// before it's ever opened, when `expr` restricted to that file's partition values can't be
+    /// satisfied (see `partition_satisfies_predicate`) — the same "prove it can't match" pruning
+    /// `process_filter` applies to Parquet row groups, just at file granularity. Surviving files
+    /// are then read and filtered concurrently, bounded by `max_concurrent_files`, and written to
+    /// one combined output.
+    ///
+    /// Assumes every surviving file shares an identical non-partition schema (same column names,
+    /// order, and types); this is the common case for a table produced by a single writer, and a
+    /// mismatch surfaces as a `ProcessingError` rather than being silently reconciled.
+    async fn process_dataset(
+        &self,
+        input_path: &str,
+        output_path: &str,
+        expr: &Predicate,
+        output_format: FileFormat,
+        columns: Option<&[String]>,
+        parquet_options: &ParquetWriteOptions,
+        max_concurrent_files: usize,
+    ) -> Result<()> {
+        log::info!(
+            "Processing dataset: prefix='{}', expr='{:?}', output_format='{:?}', max_concurrent_files={}",
+            input_path,
+            expr,
+            output_format,
+            max_concurrent_files
+        );
+
+        let prefix = input_path.trim_end_matches('/');
+        // A dataset prefix can hold an unbounded number of partition files, so page through
+        // listings via `list_objects_stream` rather than `list_objects`, which buffers every
+        // page in memory up front.
+        let mut object_pages = self.file_handler.list_objects_stream(Some(&format!("{}/", prefix)));
+        let mut dataset_files: Vec<DatasetFile> = Vec::new();
+        while let Some(page) = object_pages.next_page().await? {
+            dataset_files.extend(page.into_iter().filter(|object| !object.key.ends_with('/')).filter_map(
+                |object| match infer_file_format(&object.key) {
+                    format @ (FileFormat::Csv | FileFormat::Parquet) => {
+                        let partition_values = parse_hive_partitions(&object.key);
+                        Some(DatasetFile { key: object.key, format, partition_values })
+                    }
+                    _ => None,
+                },
+            ));
+        }
+
+        if dataset_files.is_empty() {
+            return Err(KaryakshamError::ProcessingError(format!(
+                "No CSV or Parquet files found under dataset prefix '{}'",
+                prefix
+            )));
+        }
+
+        let partition_columns = merge_partition_columns(&dataset_files)?;
+        let surviving_files: Vec<DatasetFile> = dataset_files
+            .into_iter()
+            .filter(|file| partition_satisfies_predicate(&file.partition_values, expr))
+            .collect();
+        log::info!(
+            "Dataset partition pruning for '{}': reading {} surviving file(s)",
+            prefix,
+            surviving_files.len()
+        );
+        if surviving_files.is_empty() {
+            return Err(KaryakshamError::ProcessingError(format!(
+                "No dataset file under '{}' could satisfy the filter predicate after partition pruning",
+                prefix
+            )));
+        }
+
+        let base_schema = self.merge_dataset_schema(&surviving_files).await?;
+        let full_schema = Arc::new(append_partition_fields(&base_schema, &partition_columns));
+
+        let output_sink = self.file_handler.create_file(output_path).await?;
+
+        let mut file_batches = stream::iter(
+            surviving_files
+                .iter()
+                .map(|file| self.read_dataset_file_batches(file, &full_schema)),
+        )
+        .buffer_unordered(max_concurrent_files.max(1));
+
+        match output_format {
+            FileFormat::Csv => {
+                let mut buffer: Vec<u8> = Vec::new();
+                {
+                    let mut csv_writer = WriterBuilder::new().has_headers(true).build(&mut buffer);
+                    while let Some(batches_result) = file_batches.next().await {
+                        for batch in batches_result? {
+                            let mask = compile_predicate(expr, &batch, &full_schema)?;
+                            let filtered = compute::filter_record_batch(&batch, &mask).map_err(|e| {
+                                KaryakshamError::ProcessingError(format!("Failed to filter dataset batch: {}", e))
+                            })?;
+                            let output_batch = project_batch(&filtered, &full_schema, columns)?;
+                            csv_writer.write(&output_batch).map_err(|e| {
+                                KaryakshamError::ProcessingError(format!("Failed to write filtered batch to CSV: {}", e))
+                            })?;
+                        }
+                    }
+                }
+                let mut output_sink = output_sink;
+                output_sink.write_all(&buffer).await?;
+                output_sink.flush().await?;
+            }
+            FileFormat::Parquet => {
+                let output_schema = match columns {
+                    Some(columns) => {
+                        let indices = projection_indices(&full_schema, columns)?;
+                        Arc::new(full_schema.project(&indices).map_err(|e| {
+                            KaryakshamError::ProcessingError(format!("Failed to project dataset schema: {}", e))
+                        })?)
+                    }
+                    None => full_schema.clone(),
+                };
+                let props = Some(Arc::new(build_writer_properties(parquet_options, &output_schema)?));
+                let max_buffer_bytes = parse_byte_size(parquet_options.write_buffer_size.as_deref())?;
+
+                let arrow_parquet_writer = AsyncArrowWriter::try_new(output_sink, output_schema.clone(), props)
+                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to create Parquet Arrow writer: {}", e)))?;
+                let mut bounded_writer = BoundedParquetWriter::new(arrow_parquet_writer, max_buffer_bytes);
+
+                while let Some(batches_result) = file_batches.next().await {
+                    for batch in batches_result? {
+                        let mask = compile_predicate(expr, &batch, &full_schema)?;
+                        let filtered = compute::filter_record_batch(&batch, &mask).map_err(|e| {
+                            KaryakshamError::ProcessingError(format!("Failed to filter dataset batch: {}", e))
+                        })?;
+                        let output_batch = project_batch(&filtered, &full_schema, columns)?;
+                        bounded_writer.write(&output_batch).await?;
+                    }
+                }
+                bounded_writer.close().await?;
+            }
+            other => {
+                return Err(KaryakshamError::NotImplemented(format!(
+                    "Output format {:?} not supported for dataset filter.",
+                    other
+                )))
+            }
+        }
+
+        log::info!("Dataset processing complete for prefix '{}'", prefix);
+        Ok(())
+    }
+
+    /// Infers and validates a single non-partition Arrow schema shared by every file in
+    /// `files`, opening each just far enough to read its schema (the CSV reader's header-driven
+    /// inference, or the Parquet footer). The first file's schema is canonical; any later file
+    /// whose schema disagrees on field count, name, or type surfaces as a `ProcessingError`.
+    async fn merge_dataset_schema(&self, files: &[DatasetFile]) -> Result<Schema> {
+        let mut canonical: Option<Schema> = None;
+        for file in files {
+            let schema = self.read_file_schema(file).await?;
+            match &canonical {
+                None => canonical = Some(schema),
+                Some(expected) => {
+                    if expected != &schema {
+                        return Err(KaryakshamError::ProcessingError(format!(
+                            "Dataset file '{}' has a schema incompatible with the rest of the dataset: expected {:?}, found {:?}",
+                            file.key, expected, schema
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(canonical.expect("files is non-empty, checked by process_dataset"))
+    }
+
+    /// Reads just enough of `file` to determine its non-partition Arrow schema.
+    async fn read_file_schema(&self, file: &DatasetFile) -> Result<Schema> {
+        match file.format {
+            FileFormat::Csv => {
+                let input_stream = self.file_handler.read_file(&file.key).await?;
+                let csv_reader = ArrowCsvReader::Builder::new()
+                    .has_headers(true)
+                    .build(BufReader::new(input_stream).compat())
+                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to read CSV schema for '{}': {}", file.key, e)))?;
+                Ok((*csv_reader.schema()).clone())
+            }
+            FileFormat::Parquet => {
+                let mut input_stream = self.file_handler.read_file(&file.key).await?;
+                let mut raw = Vec::new();
+                input_stream.read_to_end(&mut raw).await?;
+                let builder = ParquetRecordBatchStreamBuilder::new(Bytes::from(raw))
+                    .await
+                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to read Parquet footer metadata for '{}': {}", file.key, e)))?;
+                Ok((*builder.schema()).clone())
+            }
+            other => Err(KaryakshamError::NotImplemented(format!(
+                "Input format {:?} not supported in a dataset.",
+                other
+            ))),
+        }
+    }
+
+    /// Reads every batch of `file` in full and appends its Hive partition values as constant
+    /// columns, so the returned batches conform to `full_schema` (non-partition columns followed
+    /// by partition columns). Collected eagerly per file rather than streamed, bounded in
+    /// aggregate by `max_concurrent_files` rather than per-batch.
+    async fn read_dataset_file_batches(&self, file: &DatasetFile, full_schema: &Schema) -> Result<Vec<RecordBatch>> {
+        let raw_batches: Vec<RecordBatch> = match file.format {
+            FileFormat::Csv => {
+                let input_stream = self.file_handler.read_file(&file.key).await?;
+                let csv_reader = ArrowCsvReader::Builder::new()
+                    .has_headers(true)
+                    .build(BufReader::new(input_stream).compat())
+                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to create Arrow CSV reader for '{}': {}", file.key, e)))?;
+                csv_reader
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to read CSV batches from '{}': {}", file.key, e)))?
+            }
+            FileFormat::Parquet => {
+                let mut input_stream = self.file_handler.read_file(&file.key).await?;
+                let mut raw = Vec::new();
+                input_stream.read_to_end(&mut raw).await?;
+                let builder = ParquetRecordBatchStreamBuilder::new(Bytes::from(raw))
+                    .await
+                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to read Parquet footer metadata for '{}': {}", file.key, e)))?;
+                builder
+                    .build()
+                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to build Parquet batch stream for '{}': {}", file.key, e)))?
+                    .map(|r| r.map_err(|e| KaryakshamError::ProcessingError(format!("Failed to read Arrow record batch from '{}': {}", file.key, e))))
+                    .collect::<Vec<_>>()
+                    .await
+                    .into_iter()
+                    .collect::<Result<Vec<_>>>()?
+            }
+            other => {
+                return Err(KaryakshamError::NotImplemented(format!(
+                    "Input format {:?} not supported in a dataset.",
+                    other
+                )))
+            }
+        };
+
+        raw_batches
+            .into_iter()
+            .map(|batch| append_partition_columns(&batch, full_schema, &file.partition_values))
+            .collect()
+    }
+
+    /// Rewrites an existing Parquet file with new physical properties — compression codec,
+    /// row-group sizing, column set, bloom filters — without a CSV round-trip. Batches stream
+    /// straight from the input reader through `RecordBatchRechunker` (when `target_rows_per_group`
+    /// is set) to coalesce/split them to the requested row-group size, then into a
+    /// `BoundedParquetWriter` configured from `parquet_options`.
+    async fn process_parquet_rewrite(
+        &self,
+        input_path: &str,
+        output_path: &str,
+        columns: Option<&[String]>,
+        parquet_options: &ParquetWriteOptions,
+        target_rows_per_group: Option<usize>,
+    ) -> Result<()> {
+        log::info!(
+            "Rewriting Parquet file: input='{}', output='{}', columns='{:?}', target_rows_per_group='{:?}'",
+            input_path,
+            output_path,
+            columns,
+            target_rows_per_group
+        );
+
+        let mut input_stream = self.file_handler.read_file(input_path).await?;
+        let mut raw = Vec::new();
+        input_stream.read_to_end(&mut raw).await?;
+
+        let mut builder = ParquetRecordBatchStreamBuilder::new(Bytes::from(raw))
+            .await
+            .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to read Parquet footer metadata: {}", e)))?;
+
+        let full_schema = builder.schema().clone();
+        let schema = match columns {
+            Some(columns) => {
+                let indices = projection_indices(&full_schema, columns)?;
+                let mask = ProjectionMask::leaves(builder.parquet_schema(), indices.iter().copied());
+                builder = builder.with_projection(mask);
+                Arc::new(full_schema.project(&indices).map_err(|e| {
+                    KaryakshamError::ProcessingError(format!("Failed to project Parquet schema: {}", e))
+                })?)
+            }
+            None => full_schema,
+        };
+
+        let mut batches = builder
+            .build()
+            .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to build Parquet batch stream: {}", e)))?
+            .map(|r| {
+                r.map_err(|e| KaryakshamError::ProcessingError(format!("Failed to read Arrow record batch from Parquet: {}", e)))
+            });
+
+        let output_sink = self.file_handler.create_file(output_path).await?;
+        let props = Some(Arc::new(build_writer_properties(parquet_options, &schema)?));
+        let max_buffer_bytes = parse_byte_size(parquet_options.write_buffer_size.as_deref())?;
+
+        let arrow_parquet_writer = AsyncArrowWriter::try_new(output_sink, schema.clone(), props)
+            .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to create Parquet Arrow writer: {}", e)))?;
+        let mut bounded_writer = BoundedParquetWriter::new(arrow_parquet_writer, max_buffer_bytes);
+
+        match target_rows_per_group {
+            Some(target_rows) => {
+                let mut rechunker = RecordBatchRechunker::new(schema.clone(), target_rows);
+                while let Some(batch_result) = batches.next().await {
+                    for chunk in rechunker.push(batch_result?)? {
+                        bounded_writer.write(&chunk).await?;
+                    }
+                }
+                if let Some(remainder) = rechunker.finish()? {
+                    bounded_writer.write(&remainder).await?;
+                }
+            }
+            None => {
+                while let Some(batch_result) = batches.next().await {
+                    bounded_writer.write(&batch_result?).await?;
+                }
+            }
+        }
+
+        bounded_writer.close().await?;
+        log::info!("Parquet rewrite complete for '{}'", input_path);
+        Ok(())
+    }
+}
+
+/// Infers a job's input format from its path extension (`.parquet`/`.pq` vs. everything else,
+/// which is treated as CSV), since `ProcessingJobParams::Filter` carries only an output format.
+fn infer_file_format(path: &str) -> FileFormat {
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+
+    match extension.as_deref() {
+        Some("parquet") | Some("pq") => FileFormat::Parquet,
+        _ => FileFormat::Csv,
+    }
+}
+
+/// Builds a `WriterProperties` for a Parquet output from `options`, so compression, row-group and
+/// data-page sizing, dictionary encoding, and per-column bloom filters are all driven by the job
+/// params instead of the hardcoded SNAPPY default this writer used before. Each
+/// `bloom_filter_columns` name must be a top-level field of `schema` (an unknown name is a
+/// `ProcessingError`) and is passed to `set_column_bloom_filter_enabled` as a single-segment
+/// `ColumnPath`.
+fn build_writer_properties(
+    options: &ParquetWriteOptions,
+    schema: &Schema,
+) -> Result<parquet::file::writer::Properties> {
+    let compression = match &options.compression {
+        ParquetCompression::Uncompressed => Compression::UNCOMPRESSED,
+        ParquetCompression::Snappy => Compression::SNAPPY,
+        ParquetCompression::Gzip => Compression::GZIP(Default::default()),
+        ParquetCompression::Lz4 => Compression::LZ4,
+        ParquetCompression::Zstd { level } => Compression::ZSTD(ZstdLevel::try_new(*level).map_err(|e| {
+            KaryakshamError::ProcessingError(format!("Invalid ZSTD compression level {}: {}", level, e))
+        })?),
+        ParquetCompression::Brotli { level } => {
+            Compression::BROTLI(BrotliLevel::try_new(*level).map_err(|e| {
+                KaryakshamError::ProcessingError(format!("Invalid Brotli compression level {}: {}", level, e))
+            })?)
+        }
+    };
+
+    let mut builder = parquet::file::writer::Properties::builder()
+        .set_compression(compression)
+        .set_dictionary_enabled(options.dictionary_enabled);
+
+    if let Some(row_group_size) = options.row_group_size {
+        builder = builder.set_max_row_group_size(row_group_size);
+    }
+    if let Some(data_page_size_limit) = options.data_page_size_limit {
+        builder = builder.set_data_page_size_limit(data_page_size_limit);
+    }
+
+    for column in &options.bloom_filter_columns {
+        if schema.index_of(column).is_err() {
+            return Err(KaryakshamError::ProcessingError(format!(
+                "Bloom filter requested for unknown column '{}'",
+                column
+            )));
+        }
+        builder = builder.set_column_bloom_filter_enabled(ColumnPath::from(column.clone()), true);
+    }
+
+    Ok(builder.build())
+}
+
+/// Wraps an `AsyncArrowWriter`, flushing its buffered encoded bytes to the underlying async sink
+/// once `write()` leaves the writer's `in_progress_size()` past `max_buffer_bytes`, so a
+/// `process_filter`/`process_csv_to_parquet` Parquet output runs in bounded memory regardless of
+/// input size. `max_buffer_bytes: None` makes this a thin pass-through to the Arrow writer's own
+/// buffering behavior.
+struct BoundedParquetWriter<W: AsyncWrite + Unpin + Send> {
+    inner: AsyncArrowWriter<W>,
+    max_buffer_bytes: Option<usize>,
+}
+
+impl<W: AsyncWrite + Unpin + Send> BoundedParquetWriter<W> {
+    fn new(inner: AsyncArrowWriter<W>, max_buffer_bytes: Option<usize>) -> Self {
+        BoundedParquetWriter { inner, max_buffer_bytes }
+    }
+
+    async fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.inner
+            .write(batch)
+            .await
+            .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to write Arrow batch to Parquet: {}", e)))?;
+
+        if let Some(max_buffer_bytes) = self.max_buffer_bytes {
+            if self.inner.in_progress_size() > max_buffer_bytes {
+                self.inner
+                    .flush()
+                    .await
+                    .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to flush buffered Parquet data: {}", e)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drains any remaining buffered bytes and finalizes the Parquet footer.
+    async fn close(self) -> Result<()> {
+        self.inner
             .close()
             .await
             .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to close Parquet writer: {}", e)))?;
-
-        log::info!("CSV to Parquet conversion complete for '{}'", input_path);
         Ok(())
     }
+}
+
+/// Coalesces/splits a stream of `RecordBatch`es into batches of exactly `target_rows` rows (the
+/// final batch emitted by `finish()` may be smaller), used by `process_parquet_rewrite` to
+/// re-chunk an input file's row groups to a requested size before writing. Pushed batches are
+/// buffered and concatenated via `compute::concat_batches` only once enough rows have accumulated
+/// to cut a full chunk, so a rewrite of many small input batches doesn't re-concatenate the whole
+/// file on every push.
+struct RecordBatchRechunker {
+    schema: SchemaRef,
+    target_rows: usize,
+    pending: Vec<RecordBatch>,
+    pending_rows: usize,
+}
+
+impl RecordBatchRechunker {
+    fn new(schema: SchemaRef, target_rows: usize) -> Self {
+        RecordBatchRechunker { schema, target_rows, pending: Vec::new(), pending_rows: 0 }
+    }
+
+    /// Buffers `batch` and returns zero or more chunks of exactly `target_rows` rows that became
+    /// ready as a result.
+    fn push(&mut self, batch: RecordBatch) -> Result<Vec<RecordBatch>> {
+        self.pending_rows += batch.num_rows();
+        self.pending.push(batch);
+
+        let mut ready = Vec::new();
+        while self.pending_rows >= self.target_rows && self.target_rows > 0 {
+            let combined = compute::concat_batches(&self.schema, &self.pending).map_err(|e| {
+                KaryakshamError::ProcessingError(format!("Failed to coalesce batches for rechunking: {}", e))
+            })?;
+            ready.push(combined.slice(0, self.target_rows));
+
+            let remainder_rows = combined.num_rows() - self.target_rows;
+            self.pending_rows = remainder_rows;
+            self.pending = if remainder_rows > 0 {
+                vec![combined.slice(self.target_rows, remainder_rows)]
+            } else {
+                Vec::new()
+            };
+        }
+        Ok(ready)
+    }
+
+    /// Returns whatever rows remain buffered (fewer than `target_rows`) as a final, short chunk,
+    /// or `None` if nothing was pending.
+    fn finish(self) -> Result<Option<RecordBatch>> {
+        if self.pending.is_empty() {
+            return Ok(None);
+        }
+        let combined = compute::concat_batches(&self.schema, &self.pending).map_err(|e| {
+            KaryakshamError::ProcessingError(format!("Failed to coalesce final batches for rechunking: {}", e))
+        })?;
+        Ok(Some(combined))
+    }
+}
+
+/// Parses a human-readable byte size like `"64MB"`, `"512 KiB"`, or a bare `"1048576"` into a byte
+/// count. Recognizes `KB`/`MB`/`GB` (1000-based) and `KiB`/`MiB`/`GiB` (1024-based) suffixes,
+/// case-insensitively, with optional whitespace before the suffix. Returns `None` when `size` is
+/// `None`; a present-but-unparsable string surfaces as a `ProcessingError`.
+fn parse_byte_size(size: Option<&str>) -> Result<Option<usize>> {
+    let Some(size) = size else { return Ok(None) };
+    let trimmed = size.trim();
+    let split_at = trimmed.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(trimmed.len());
+    let (number, suffix) = trimmed.split_at(split_at);
+
+    let invalid = || KaryakshamError::ProcessingError(format!("Invalid byte size '{}'", size));
+
+    let value: f64 = number.trim().parse().map_err(|_| invalid())?;
+    let multiplier: f64 = match suffix.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1.0,
+        "kb" => 1_000.0,
+        "mb" => 1_000_000.0,
+        "gb" => 1_000_000_000.0,
+        "kib" => 1024.0,
+        "mib" => 1024.0 * 1024.0,
+        "gib" => 1024.0 * 1024.0 * 1024.0,
+        _ => return Err(invalid()),
+    };
+
+    Ok(Some((value * multiplier) as usize))
+}
+
+/// A single file discovered under a `Dataset` job's prefix, together with the Hive partition
+/// values parsed from its key.
+#[derive(Debug, Clone)]
+struct DatasetFile {
+    key: String,
+    format: FileFormat,
+    /// `(column, value)` pairs parsed from `key=value` path segments, in path order.
+    partition_values: Vec<(String, String)>,
+}
+
+/// Parses Hive-style `key=value` path segments out of an object key, e.g.
+/// `events/year=2024/month=03/part-0000.parquet` yields `[("year", "2024"), ("month", "03")]`.
+/// The final segment (the file name itself) is never treated as a partition segment.
+fn parse_hive_partitions(key: &str) -> Vec<(String, String)> {
+    let mut segments: Vec<&str> = key.split('/').collect();
+    segments.pop(); // drop the file name
+    segments
+        .into_iter()
+        .filter_map(|segment| segment.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// Determines the set of Hive partition column names shared by every file in `files`, in the
+/// order the first file's path introduced them. Files disagreeing on which partition columns are
+/// present surface as a `ProcessingError`, since a dataset with inconsistent partitioning can't be
+/// given a single schema.
+fn merge_partition_columns(files: &[DatasetFile]) -> Result<Vec<String>> {
+    let first = &files[0];
+    let expected: Vec<String> = first.partition_values.iter().map(|(k, _)| k.clone()).collect();
+
+    for file in &files[1..] {
+        let found: Vec<String> = file.partition_values.iter().map(|(k, _)| k.clone()).collect();
+        if found != expected {
+            return Err(KaryakshamError::ProcessingError(format!(
+                "Dataset file '{}' has partition columns {:?}, but '{}' has {:?}",
+                file.key, found, first.key, expected
+            )));
+        }
+    }
+    Ok(expected)
+}
+
+/// Appends a nullable `Utf8` field for each name in `partition_columns` to `schema`, giving the
+/// schema of a dataset batch once its virtual partition columns are attached (see
+/// `append_partition_columns`).
+fn append_partition_fields(schema: &Schema, partition_columns: &[String]) -> Schema {
+    let mut fields: Vec<Field> = schema.fields().iter().map(|f| f.as_ref().clone()).collect();
+    for column in partition_columns {
+        fields.push(Field::new(column, DataType::Utf8, true));
+    }
+    Schema::new(fields)
+}
+
+/// Appends one constant-valued `StringArray` column per entry in `partition_values` to `batch`,
+/// producing a batch that conforms to `full_schema` (the file's own columns followed by its
+/// partition columns, per `append_partition_fields`).
+fn append_partition_columns(
+    batch: &RecordBatch,
+    full_schema: &Schema,
+    partition_values: &[(String, String)],
+) -> Result<RecordBatch> {
+    let mut columns: Vec<ArrayRef> = batch.columns().to_vec();
+    for (_, value) in partition_values {
+        columns.push(Arc::new(StringArray::from(vec![value.clone(); batch.num_rows()])));
+    }
+    RecordBatch::try_new(Arc::new(full_schema.clone()), columns)
+        .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to attach partition columns to batch: {}", e)))
+}
+
+/// Recursively evaluates whether `predicate`, restricted to the Hive partition columns present in
+/// `partition_values`, could still be satisfied by some row in a file carrying those partition
+/// values — mirroring `row_group_possibly_matches`'s "prove it can't match" pruning, just decided
+/// once per file from its partition values rather than per row group from column statistics. A
+/// leaf referencing a non-partition (data) column can't be decided here and is treated as "can't
+/// rule out" (`true`); the real row-level filter still applies once the file is read.
+fn partition_satisfies_predicate(partition_values: &[(String, String)], predicate: &Predicate) -> bool {
+    match predicate {
+        Predicate::Compare { column, op, literal } => partition_values
+            .iter()
+            .find(|(k, _)| k == column)
+            .map(|(_, value)| compare_partition_value(op, value, literal))
+            .unwrap_or(true),
+        Predicate::In { column, values } => partition_values
+            .iter()
+            .find(|(k, _)| k == column)
+            .map(|(_, value)| values.iter().any(|literal| compare_partition_value(&ComparisonOp::Eq, value, literal)))
+            .unwrap_or(true),
+        Predicate::And(lhs, rhs) => {
+            partition_satisfies_predicate(partition_values, lhs) && partition_satisfies_predicate(partition_values, rhs)
+        }
+        Predicate::Or(lhs, rhs) => {
+            partition_satisfies_predicate(partition_values, lhs) || partition_satisfies_predicate(partition_values, rhs)
+        }
+        Predicate::Not(_) => true,
+    }
+}
+
+/// Compares a partition's string value against `literal` using `op`. Partition values are always
+/// strings (parsed straight out of the path), so every literal is rendered to its string form
+/// first; this makes `Lt`/`Le`/`Gt`/`Ge` lexicographic rather than numeric, which matches typical
+/// zero-padded Hive partition conventions (`month=03`) but is a known simplification for
+/// unpadded numeric partitions.
+fn compare_partition_value(op: &ComparisonOp, value: &str, literal: &PredicateLiteral) -> bool {
+    let literal_str = match literal {
+        PredicateLiteral::String(s) => s.clone(),
+        PredicateLiteral::Int(i) => i.to_string(),
+        PredicateLiteral::Float(f) => f.to_string(),
+        PredicateLiteral::Bool(b) => b.to_string(),
+        PredicateLiteral::Timestamp(ts) => ts.to_string(),
+    };
+
+    match op {
+        ComparisonOp::Eq => value == literal_str,
+        ComparisonOp::Ne => value != literal_str,
+        ComparisonOp::Lt => value < literal_str.as_str(),
+        ComparisonOp::Le => value <= literal_str.as_str(),
+        ComparisonOp::Gt => value > literal_str.as_str(),
+        ComparisonOp::Ge => value >= literal_str.as_str(),
+    }
+}
+
+/// Applies an optional output column projection to `batch` by name, resolved against `schema`.
+/// `None` returns `batch` unchanged.
+fn project_batch(batch: &RecordBatch, schema: &Schema, columns: Option<&[String]>) -> Result<RecordBatch> {
+    match columns {
+        Some(columns) => {
+            let indices = projection_indices(schema, columns)?;
+            batch
+                .project(&indices)
+                .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to project output batch: {}", e)))
+        }
+        None => Ok(batch.clone()),
+    }
+}
+
+/// Resolves a requested column projection against `schema`, returning the matching field indices
+/// in the same order as `columns`. An unknown column name surfaces as a `ProcessingError` rather
+/// than being silently dropped.
+fn projection_indices(schema: &Schema, columns: &[String]) -> Result<Vec<usize>> {
+    columns
+        .iter()
+        .map(|column| {
+            schema
+                .index_of(column)
+                .map_err(|_| KaryakshamError::ProcessingError(format!("Projected column '{}' not found in schema", column)))
+        })
+        .collect()
+}
+
+/// Determines which row groups in `builder`'s Parquet file could possibly satisfy `expr`, so only
+/// those need to be decoded. A row group is skipped when a `Compare`/`In` leaf's column statistics
+/// (min/max) prove the literal can't be present, or — for equality comparisons — when the
+/// column's bloom filter reports the literal definitely absent. `And` skips a group if either
+/// child alone rules it out; `Or` only if both children do; `Not` is never pruned, since a
+/// negated condition can't be ruled out from column bounds alone.
+async fn prune_row_groups(
+    builder: &mut ParquetRecordBatchStreamBuilder<Bytes>,
+    schema: &Schema,
+    expr: &Predicate,
+) -> Result<Vec<usize>> {
+    let row_group_count = builder.metadata().row_groups().len();
+    let mut surviving = Vec::with_capacity(row_group_count);
+
+    for row_group_idx in 0..row_group_count {
+        if row_group_possibly_matches(builder, row_group_idx, schema, expr).await? {
+            surviving.push(row_group_idx);
+        }
+    }
+    Ok(surviving)
+}
+
+/// Recursively evaluates whether row group `row_group_idx` could contain a row matching
+/// `predicate`, consulting column statistics and (for equality leaves) bloom filters. Boxed
+/// because `async fn` can't recurse directly.
+fn row_group_possibly_matches<'a>(
+    builder: &'a mut ParquetRecordBatchStreamBuilder<Bytes>,
+    row_group_idx: usize,
+    schema: &'a Schema,
+    predicate: &'a Predicate,
+) -> BoxFuture<'a, Result<bool>> {
+    async move {
+        match predicate {
+            Predicate::Compare { column, op, literal } => {
+                let Ok(column_idx) = schema.index_of(column) else { return Ok(true) };
+                let row_group = builder.metadata().row_group(row_group_idx).clone();
+                if row_group_excluded_by_statistics(&row_group, column_idx, op, literal) {
+                    return Ok(false);
+                }
+                if matches!(op, ComparisonOp::Eq)
+                    && row_group_excluded_by_bloom_filter(builder, row_group_idx, column_idx, literal).await?
+                {
+                    return Ok(false);
+                }
+                Ok(true)
+            }
+            Predicate::In { column, values } => {
+                let Ok(column_idx) = schema.index_of(column) else { return Ok(true) };
+                let row_group = builder.metadata().row_group(row_group_idx).clone();
+                for value in values {
+                    if row_group_excluded_by_statistics(&row_group, column_idx, &ComparisonOp::Eq, value) {
+                        continue;
+                    }
+                    if !row_group_excluded_by_bloom_filter(builder, row_group_idx, column_idx, value).await? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Predicate::And(lhs, rhs) => Ok(row_group_possibly_matches(builder, row_group_idx, schema, lhs).await?
+                && row_group_possibly_matches(builder, row_group_idx, schema, rhs).await?),
+            Predicate::Or(lhs, rhs) => Ok(row_group_possibly_matches(builder, row_group_idx, schema, lhs).await?
+                || row_group_possibly_matches(builder, row_group_idx, schema, rhs).await?),
+            Predicate::Not(_) => Ok(true),
+        }
+    }
+    .boxed()
+}
+
+/// Tests `literal` against column `column_idx`'s recorded min/max statistics for `row_group`,
+/// returning `true` only when the statistics prove no row in the group can satisfy `op`. Returns
+/// `false` (i.e. "can't rule it out") whenever statistics are absent or of an incomparable type.
+fn row_group_excluded_by_statistics(
+    row_group: &RowGroupMetaData,
+    column_idx: usize,
+    op: &ComparisonOp,
+    literal: &PredicateLiteral,
+) -> bool {
+    let Some(stats) = row_group.column(column_idx).statistics() else {
+        return false;
+    };
+    let Some((min_cmp, max_cmp)) = compare_to_min_max(stats, literal) else {
+        return false;
+    };
+
+    match op {
+        ComparisonOp::Eq => min_cmp == Ordering::Greater || max_cmp == Ordering::Less,
+        ComparisonOp::Ne => min_cmp == Ordering::Equal && max_cmp == Ordering::Equal,
+        ComparisonOp::Lt => min_cmp != Ordering::Less,
+        ComparisonOp::Le => min_cmp == Ordering::Greater,
+        ComparisonOp::Gt => max_cmp != Ordering::Greater,
+        ComparisonOp::Ge => max_cmp == Ordering::Less,
+    }
+}
+
+/// Compares `literal` against `stats`' min and max, returning `(min.cmp(literal),
+/// max.cmp(literal))` when the statistics' physical type lines up with the literal's type, or
+/// `None` when it doesn't (e.g. a string literal against numeric statistics).
+fn compare_to_min_max(stats: &Statistics, literal: &PredicateLiteral) -> Option<(Ordering, Ordering)> {
+    match (stats, literal) {
+        (Statistics::Int32(s), PredicateLiteral::Int(v)) => {
+            Some(((*s.min() as i64).cmp(v), (*s.max() as i64).cmp(v)))
+        }
+        (Statistics::Int64(s), PredicateLiteral::Int(v)) => Some((s.min().cmp(v), s.max().cmp(v))),
+        (Statistics::Int64(s), PredicateLiteral::Timestamp(v)) => Some((s.min().cmp(v), s.max().cmp(v))),
+        (Statistics::Float(s), PredicateLiteral::Float(v)) => {
+            Some((s.min().partial_cmp(&(*v as f32))?, s.max().partial_cmp(&(*v as f32))?))
+        }
+        (Statistics::Double(s), PredicateLiteral::Float(v)) => {
+            Some((s.min().partial_cmp(v)?, s.max().partial_cmp(v)?))
+        }
+        (Statistics::Boolean(s), PredicateLiteral::Bool(v)) => Some((s.min().cmp(v), s.max().cmp(v))),
+        (Statistics::ByteArray(s), PredicateLiteral::String(v)) => {
+            let min = std::str::from_utf8(s.min().data()).ok()?;
+            let max = std::str::from_utf8(s.max().data()).ok()?;
+            Some((min.cmp(v.as_str()), max.cmp(v.as_str())))
+        }
+        _ => None,
+    }
+}
+
+/// Consults row group `row_group_idx`'s bloom filter for column `column_idx`, returning `true`
+/// only when a filter is present and reports `literal` as definitely absent. Returns `false` when
+/// no bloom filter was written for this column (can't prune) or for boolean literals (bloom
+/// filters aren't a useful pruning tool for a two-valued domain).
+async fn row_group_excluded_by_bloom_filter(
+    builder: &mut ParquetRecordBatchStreamBuilder<Bytes>,
+    row_group_idx: usize,
+    column_idx: usize,
+    literal: &PredicateLiteral,
+) -> Result<bool> {
+    let bloom_filter = builder
+        .get_row_group_column_bloom_filter(row_group_idx, column_idx)
+        .await
+        .map_err(|e| {
+            KaryakshamError::ProcessingError(format!(
+                "Failed to read bloom filter for row group {} column {}: {}",
+                row_group_idx, column_idx, e
+            ))
+        })?;
+
+    let Some(bloom_filter) = bloom_filter else {
+        return Ok(false);
+    };
+
+    let definitely_present = match literal {
+        PredicateLiteral::String(s) => bloom_filter.check(&s.as_str()),
+        PredicateLiteral::Int(i) => bloom_filter.check(i),
+        PredicateLiteral::Float(f) => bloom_filter.check(f),
+        PredicateLiteral::Timestamp(ts) => bloom_filter.check(ts),
+        PredicateLiteral::Bool(_) => return Ok(false),
+    };
+
+    Ok(!definitely_present)
+}
+
+/// Evaluates `predicate` against `batch`, producing a `BooleanArray` mask suitable for
+/// `compute::filter_record_batch`. Leaves resolve their column through `schema` and compile to
+/// the matching `arrow::compute` comparison kernel against the column's own `DataType`; `And`/
+/// `Or`/`Not` recursively compile their children and merge masks with the equivalent kernel.
+fn compile_predicate(predicate: &Predicate, batch: &RecordBatch, schema: &Schema) -> Result<BooleanArray> {
+    match predicate {
+        Predicate::Compare { column, op, literal } => {
+            let col_array = column_by_name(batch, schema, column)?;
+            let literal_array = literal_to_array(literal, col_array.data_type())?;
+            let mask = match op {
+                ComparisonOp::Eq => compute::eq(col_array, &literal_array),
+                ComparisonOp::Ne => compute::neq(col_array, &literal_array),
+                ComparisonOp::Lt => compute::lt(col_array, &literal_array),
+                ComparisonOp::Le => compute::lt_eq(col_array, &literal_array),
+                ComparisonOp::Gt => compute::gt(col_array, &literal_array),
+                ComparisonOp::Ge => compute::gt_eq(col_array, &literal_array),
+            }
+            .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to compare column '{}': {}", column, e)))?;
+            Ok(mask)
+        }
+        Predicate::In { column, values } => {
+            let col_array = column_by_name(batch, schema, column)?;
+            let mut combined: Option<BooleanArray> = None;
+            for value in values {
+                let literal_array = literal_to_array(value, col_array.data_type())?;
+                let mask = compute::eq(col_array, &literal_array).map_err(|e| {
+                    KaryakshamError::ProcessingError(format!("Failed to compare column '{}' for IN: {}", column, e))
+                })?;
+                combined = Some(match combined {
+                    Some(acc) => compute::or(&acc, &mask)
+                        .map_err(|e| KaryakshamError::ProcessingError(format!("Failed to combine IN values for column '{}': {}", column, e)))?,
+                    None => mask,
+                });
+            }
+            combined.ok_or_else(|| KaryakshamError::ProcessingError(format!("IN predicate for column '{}' has no values", column)))
+        }
+        Predicate::And(lhs, rhs) => {
+            let left = compile_predicate(lhs, batch, schema)?;
+            let right = compile_predicate(rhs, batch, schema)?;
+            compute::and(&left, &right).map_err(|e| KaryakshamError::ProcessingError(format!("Failed to AND predicate masks: {}", e)))
+        }
+        Predicate::Or(lhs, rhs) => {
+            let left = compile_predicate(lhs, batch, schema)?;
+            let right = compile_predicate(rhs, batch, schema)?;
+            compute::or(&left, &right).map_err(|e| KaryakshamError::ProcessingError(format!("Failed to OR predicate masks: {}", e)))
+        }
+        Predicate::Not(inner) => {
+            let mask = compile_predicate(inner, batch, schema)?;
+            compute::not(&mask).map_err(|e| KaryakshamError::ProcessingError(format!("Failed to NOT predicate mask: {}", e)))
+        }
+    }
+}
+
+/// Looks up `column` in `schema` and returns the matching column from `batch`, surfacing an
+/// unknown column name as a `ProcessingError` rather than a panic.
+fn column_by_name<'a>(batch: &'a RecordBatch, schema: &Schema, column: &str) -> Result<&'a ArrayRef> {
+    let idx = schema
+        .index_of(column)
+        .map_err(|_| KaryakshamError::ProcessingError(format!("Filter column '{}' not found in schema", column)))?;
+    Ok(batch.column(idx))
+}
+
+/// Builds a single-element Arrow array for `literal` and casts it to `data_type`, so a literal
+/// parsed from JSON (always one of a handful of Rust types) ends up comparable against whatever
+/// Arrow type the CSV reader inferred for the target column. A literal that can't be cast (e.g. a
+/// string literal against a numeric column) surfaces as a `ProcessingError`.
+fn literal_to_array(literal: &PredicateLiteral, data_type: &DataType) -> Result<ArrayRef> {
+    let raw: ArrayRef = match literal {
+        PredicateLiteral::String(s) => Arc::new(StringArray::from(vec![s.clone()])),
+        PredicateLiteral::Int(i) => Arc::new(Int64Array::from(vec![*i])),
+        PredicateLiteral::Float(f) => Arc::new(Float64Array::from(vec![*f])),
+        PredicateLiteral::Bool(b) => Arc::new(BooleanArray::from(vec![*b])),
+        PredicateLiteral::Timestamp(ts) => Arc::new(TimestampMillisecondArray::from(vec![*ts])),
+    };
+
+    compute::cast(&raw, data_type).map_err(|e| {
+        KaryakshamError::ProcessingError(format!(
+            "Literal {:?} cannot be cast to column type {:?}: {}",
+            literal, data_type, e
+        ))
+    })
+}
+
+// --- CSV row pipeline for `process_csv_file` / `process_csv_file_async` ---
+//
+// Everything above this point belongs to the dataset-oriented `DataProcessor`/Arrow/Parquet
+// engine. `lib.rs`'s `process_csv_file` calls `process_csv_data(ByteStream, CsvProcessingParams,
+// Option<PyObject>)`
+// directly, against the lighter row-oriented types (`FilterCondition`, `Transformation`,
+// `Aggregation`) it deserializes from `processing_params_json` — a separate, simpler pipeline from
+// the `DataProcessor` engine above, so it's kept in its own section rather than threaded through
+// that engine's `RecordBatch`/predicate machinery.
+
+/// Number of rows handed to each rayon task at a time when filtering, transforming, or
+/// aggregating. Large enough to amortize the per-task overhead, small enough that one slow chunk
+/// doesn't dominate the wall-clock of the parallel phase.
+const ROW_CHUNK_SIZE: usize = 1024;
+
+/// A single parsed CSV row, keyed by column name.
+type Row = HashMap<String, serde_json::Value>;
+
+/// Parses `input` as CSV according to `params`, applies its filters and transformations with a
+/// rayon-backed parallel execution path, and serializes the result back to CSV bytes.
+///
+/// `progress_callback`, if given, is invoked at each pipeline stage boundary (parse, filter,
+/// each transformation, column selection, serialize) with `{rows_processed, bytes_read,
+/// percent}`. Returning `False` (or raising) from the callback aborts the pipeline with a
+/// `KaryakshamError::ProcessingError("cancelled")`.
+pub async fn process_csv_data(
+    input: ByteStream,
+    params: CsvProcessingParams,
+    progress_callback: Option<PyObject>,
+) -> Result<ByteStream> {
+    let bytes = input
+        .collect()
+        .await
+        .context("Failed to read CSV byte stream into memory")
+        .map_err(KaryakshamError::IoError)?
+        .into_bytes();
+    let total_bytes = bytes.len() as u64;
+
+    let delimiter = params.delimiter.unwrap_or(',') as u8;
+    let has_header = params.has_header.unwrap_or(true);
+
+    let mut rows = parse_csv_rows(&bytes, delimiter, has_header)?;
+    let total_rows = rows.len() as u64;
+    report_progress(&progress_callback, total_rows, total_bytes, 0.0)?;
+
+    if let Some(filters) = &params.filters {
+        rows = filter_rows(rows, filters);
+    }
+    report_progress(&progress_callback, total_rows, total_bytes, 30.0)?;
+
+    if let Some(Some(transformations)) = &params.transformations {
+        let step = if transformations.is_empty() { 0.0 } else { 40.0 / transformations.len() as f64 };
+        for (i, transformation) in transformations.iter().enumerate() {
+            rows = apply_transformation(rows, transformation)?;
+            report_progress(&progress_callback, total_rows, total_bytes, 30.0 + step * (i + 1) as f64)?;
+        }
+    }
+
+    if let Some(columns) = &params.columns_to_select {
+        rows = rows
+            .into_par_iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .filter_map(|c| row.get(c).map(|v| (c.clone(), v.clone())))
+                    .collect::<Row>()
+            })
+            .collect();
+    }
+    report_progress(&progress_callback, total_rows, total_bytes, 90.0)?;
+
+    let output_bytes = serialize_rows(&rows, delimiter)?;
+    report_progress(&progress_callback, total_rows, total_bytes, 100.0)?;
+    Ok(ByteStream::from(output_bytes))
+}
+
+/// Invokes `callback` (if present) with `{rows_processed, bytes_read, percent}`, holding the GIL
+/// only for the duration of the call. Returning `False` from the callback, or the callback
+/// raising, is treated as a cooperative cancel signal and surfaces as a `ProcessingError`.
+fn report_progress(callback: &Option<PyObject>, rows_processed: u64, bytes_read: u64, percent: f64) -> Result<()> {
+    let Some(callback) = callback else {
+        return Ok(());
+    };
+
+    Python::with_gil(|py| {
+        let progress = PyDict::new(py);
+        progress
+            .set_item("rows_processed", rows_processed)
+            .and_then(|_| progress.set_item("bytes_read", bytes_read))
+            .and_then(|_| progress.set_item("percent", percent))
+            .map_err(|e| KaryakshamError::ProcessingError(anyhow::anyhow!("Failed to build progress payload: {}", e)))?;
+
+        match callback.call1(py, (progress,)) {
+            Ok(result) if matches!(result.extract::<bool>(py), Ok(false)) => {
+                Err(KaryakshamError::ProcessingError(anyhow::anyhow!("cancelled")))
+            }
+            Ok(_) => Ok(()),
+            Err(py_err) => {
+                log::warn!("progress_callback raised, treating it as a cancel signal: {}", py_err);
+                Err(KaryakshamError::ProcessingError(anyhow::anyhow!("cancelled")))
+            }
+        }
+    })
+}
+
+fn parse_csv_rows(bytes: &Bytes, delimiter: u8, has_header: bool) -> Result<Vec<Row>> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(has_header)
+        .from_reader(bytes.as_ref());
+
+    let headers: Vec<String> = if has_header {
+        reader
+            .headers()
+            .context("Failed to read CSV header row")
+            .map_err(KaryakshamError::ParameterError)?
+            .iter()
+            .map(|h| h.to_string())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut rows = Vec::new();
+    for record_result in reader.records() {
+        let record = record_result
+            .context("Failed to parse a CSV record")
+            .map_err(KaryakshamError::ParameterError)?;
+        let mut row = Row::new();
+        for (i, field) in record.iter().enumerate() {
+            // Positionally-named columns (`col0`, `col1`, ...) when there's no header, so
+            // filters/transformations always have a name to address.
+            let column_name = headers.get(i).cloned().unwrap_or_else(|| format!("col{}", i));
+            row.insert(column_name, infer_value(field));
+        }
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+/// Infers a JSON-typed value for a raw CSV field: integer, then float, then boolean, falling back
+/// to string. This is what lets filters and aggregations compare/sum fields numerically instead of
+/// treating every cell as an opaque string.
+fn infer_value(field: &str) -> serde_json::Value {
+    if let Ok(i) = field.parse::<i64>() {
+        serde_json::Value::from(i)
+    } else if let Ok(f) = field.parse::<f64>() {
+        serde_json::Value::from(f)
+    } else if let Ok(b) = field.parse::<bool>() {
+        serde_json::Value::from(b)
+    } else {
+        serde_json::Value::from(field)
+    }
+}
+
+/// Evaluates `conditions` against `row`, ANDing them together (the same interpretation
+/// `FilterCondition` lists get everywhere else they're consumed in this crate).
+fn row_matches(row: &Row, conditions: &[FilterCondition]) -> bool {
+    conditions.iter().all(|condition| match row.get(&condition.column) {
+        Some(actual) => apply_filter(actual, condition),
+        None => false,
+    })
+}
+
+fn apply_filter(actual: &serde_json::Value, condition: &FilterCondition) -> bool {
+    match condition.operator.as_str() {
+        "eq" => values_equal(actual, &condition.value),
+        "ne" => !values_equal(actual, &condition.value),
+        "gt" => compare_numeric(actual, &condition.value) == Some(Ordering::Greater),
+        "lt" => compare_numeric(actual, &condition.value) == Some(Ordering::Less),
+        "ge" => matches!(compare_numeric(actual, &condition.value), Some(Ordering::Greater) | Some(Ordering::Equal)),
+        "le" => matches!(compare_numeric(actual, &condition.value), Some(Ordering::Less) | Some(Ordering::Equal)),
+        "contains" => actual
+            .as_str()
+            .zip(condition.value.as_string())
+            .map_or(false, |(a, b)| a.contains(b)),
+        "starts_with" => actual
+            .as_str()
+            .zip(condition.value.as_string())
+            .map_or(false, |(a, b)| a.starts_with(b)),
+        _ => false,
+    }
+}
+
+fn values_equal(actual: &serde_json::Value, expected: &FilterValue) -> bool {
+    match expected {
+        FilterValue::String(s) => actual.as_str().map_or(false, |a| a == s),
+        FilterValue::Number(n) => actual.as_f64().map_or(false, |a| a == *n),
+        FilterValue::Boolean(b) => actual.as_bool().map_or(false, |a| a == *b),
+    }
+}
+
+fn compare_numeric(actual: &serde_json::Value, expected: &FilterValue) -> Option<Ordering> {
+    let a = actual.as_f64()?;
+    let b = expected.as_f64()?;
+    a.partial_cmp(&b)
+}
+
+/// Rayon-backed filter: partitions `rows` into chunks and evaluates the predicate across chunks in
+/// parallel, then flattens back into a single `Vec` preserving each chunk's relative input order.
+fn filter_rows(rows: Vec<Row>, filters: &[FilterCondition]) -> Vec<Row> {
+    rows.par_chunks(ROW_CHUNK_SIZE)
+        .flat_map(|chunk| chunk.iter().filter(|row| row_matches(row, filters)).cloned().collect::<Vec<_>>())
+        .collect()
+}
+
+fn apply_transformation(rows: Vec<Row>, transformation: &Transformation) -> Result<Vec<Row>> {
+    match transformation {
+        Transformation::RenameColumn { from_column, to_column } => Ok(rows
+            .into_par_iter()
+            .map(|mut row| {
+                if let Some(value) = row.remove(from_column) {
+                    row.insert(to_column.clone(), value);
+                }
+                row
+            })
+            .collect()),
+
+        Transformation::AddColumn { name, value, from_expression } => {
+            if *from_expression {
+                let expr_source = value.as_str().ok_or_else(|| {
+                    KaryakshamError::ParameterError(anyhow::anyhow!(
+                        "add_column '{}' has from_expression=true but its value is not a string expression",
+                        name
+                    ))
+                })?;
+                let expr = parse_expression(expr_source)?;
+                return rows
+                    .into_par_iter()
+                    .map(|mut row| {
+                        let computed = evaluate_expr(&expr, &row)?;
+                        row.insert(name.clone(), computed);
+                        Ok(row)
+                    })
+                    .collect();
+            }
+            Ok(rows
+                .into_par_iter()
+                .map(|mut row| {
+                    row.insert(name.clone(), value.clone());
+                    row
+                })
+                .collect())
+        }
+
+        Transformation::Aggregate { group_by_columns, aggregations } => {
+            Ok(aggregate_rows(rows, group_by_columns.as_deref().unwrap_or(&[]), aggregations))
+        }
+    }
+}
+
+/// Running per-group, per-aggregation accumulator. Each rayon chunk builds its own set of these,
+/// and partials are combined with the associative `merge` below, so the reduction is correct
+/// regardless of how the input was partitioned.
+#[derive(Clone, Default)]
+struct Accumulator {
+    sum: f64,
+    count: u64,
+    min: Option<f64>,
+    max: Option<f64>,
+    sum_of_squares: f64,
+}
+
+impl Accumulator {
+    fn add(&mut self, value: f64) {
+        self.sum += value;
+        self.sum_of_squares += value * value;
+        self.count += 1;
+        self.min = Some(self.min.map_or(value, |m| m.min(value)));
+        self.max = Some(self.max.map_or(value, |m| m.max(value)));
+    }
+
+    fn merge(&self, other: &Accumulator) -> Accumulator {
+        Accumulator {
+            sum: self.sum + other.sum,
+            sum_of_squares: self.sum_of_squares + other.sum_of_squares,
+            count: self.count + other.count,
+            min: match (self.min, other.min) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, None) => a,
+                (None, b) => b,
+            },
+            max: match (self.max, other.max) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, None) => a,
+                (None, b) => b,
+            },
+        }
+    }
+
+    fn finalize(&self, operation: &str) -> serde_json::Value {
+        match operation {
+            "sum" => serde_json::Value::from(self.sum),
+            "count" => serde_json::Value::from(self.count),
+            "min" => self.min.map(serde_json::Value::from).unwrap_or(serde_json::Value::Null),
+            "max" => self.max.map(serde_json::Value::from).unwrap_or(serde_json::Value::Null),
+            "mean" => {
+                if self.count == 0 {
+                    serde_json::Value::Null
+                } else {
+                    serde_json::Value::from(self.sum / self.count as f64)
+                }
+            }
+            "std" => {
+                if self.count == 0 {
+                    serde_json::Value::Null
+                } else {
+                    let mean = self.sum / self.count as f64;
+                    let variance = (self.sum_of_squares / self.count as f64) - mean * mean;
+                    serde_json::Value::from(variance.max(0.0).sqrt())
+                }
+            }
+            _ => serde_json::Value::Null,
+        }
+    }
+}
+
+/// Key identifying one accumulator: the group's values for `group_by_columns`, plus the
+/// (column, operation) pair of the aggregation it's tracking.
+type AccumulatorKey = (Vec<String>, String, String);
+
+/// Parallel group-by reduce: each rayon chunk builds its own partial accumulators keyed by
+/// `(group, column, operation)`, then all chunks' partials are combined with the associative
+/// `Accumulator::merge`.
+fn aggregate_rows(rows: Vec<Row>, group_by_columns: &[String], aggregations: &[Aggregation]) -> Vec<Row> {
+    let group_key = |row: &Row| -> Vec<String> {
+        group_by_columns.iter().map(|c| row.get(c).map(|v| v.to_string()).unwrap_or_default()).collect()
+    };
+
+    let partials: HashMap<AccumulatorKey, Accumulator> = rows
+        .par_chunks(ROW_CHUNK_SIZE)
+        .map(|chunk| {
+            let mut local: HashMap<AccumulatorKey, Accumulator> = HashMap::new();
+            for row in chunk {
+                let group = group_key(row);
+                for agg in aggregations {
+                    if let Some(value) = row.get(&agg.column).and_then(|v| v.as_f64()) {
+                        local
+                            .entry((group.clone(), agg.column.clone(), agg.operation.clone()))
+                            .or_default()
+                            .add(value);
+                    }
+                }
+            }
+            local
+        })
+        .reduce(HashMap::new, |mut a, b| {
+            for (key, acc) in b {
+                a.entry(key)
+                    .and_modify(|existing| *existing = existing.merge(&acc))
+                    .or_insert(acc);
+            }
+            a
+        });
+
+    // Re-group the flat `(group, column, operation) -> Accumulator` map back into one output row
+    // per distinct group.
+    let mut rows_by_group: HashMap<Vec<String>, Row> = HashMap::new();
+    for ((group, column, operation), acc) in &partials {
+        let row = rows_by_group.entry(group.clone()).or_insert_with(|| {
+            let mut row = Row::new();
+            for (i, col) in group_by_columns.iter().enumerate() {
+                row.insert(col.clone(), serde_json::Value::from(group[i].clone()));
+            }
+            row
+        });
+        let agg = aggregations.iter().find(|a| &a.column == column && &a.operation == operation);
+        let output_column = agg
+            .and_then(|a| a.new_column_name.clone())
+            .unwrap_or_else(|| format!("{}_{}", column, operation));
+        row.insert(output_column, acc.finalize(operation));
+    }
+
+    rows_by_group.into_values().collect()
+}
+
+fn serialize_rows(rows: &[Row], delimiter: u8) -> Result<Bytes> {
+    let mut columns: Vec<String> = Vec::new();
+    for row in rows {
+        for key in row.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+
+    let mut writer = WriterBuilder::new().delimiter(delimiter).from_writer(Vec::new());
+
+    writer
+        .write_record(&columns)
+        .context("Failed to write CSV header row")
+        .map_err(KaryakshamError::ProcessingError)?;
+
+    for row in rows {
+        let record: Vec<String> = columns.iter().map(|c| row.get(c).map(value_to_csv_field).unwrap_or_default()).collect();
+        writer
+            .write_record(&record)
+            .context("Failed to write CSV row")
+            .map_err(KaryakshamError::ProcessingError)?;
+    }
+
+    let written = writer
+        .into_inner()
+        .map_err(|e| anyhow::anyhow!(e.to_string()))
+        .context("Failed to finalize CSV writer")
+        .map_err(KaryakshamError::ProcessingError)?;
+
+    Ok(Bytes::from(written))
+}
+
+fn value_to_csv_field(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+// --- Computed-column expression evaluator, for `AddColumn { from_expression: true }` ---
+//
+// A small recursive-descent parser/evaluator over `Row`'s typed fields: column references
+// (`col("price")` or a bare identifier), numeric/string literals, `+ - * /`, comparisons
+// (`== != > < >= <=`), and a handful of functions (`upper`, `lower`, `len`, `round`, `coalesce`).
+// Parse failures surface as a `KaryakshamError::ParameterError` naming the offending token and its
+// position, rather than panicking on a malformed expression string.
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Column(String),
+    Number(f64),
+    Str(String),
+    Bool(bool),
+    BinaryOp(Box<Expr>, BinOp, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '+' => { tokens.push((Token::Plus, start)); i += 1; }
+            '-' => { tokens.push((Token::Minus, start)); i += 1; }
+            '*' => { tokens.push((Token::Star, start)); i += 1; }
+            '/' => { tokens.push((Token::Slash, start)); i += 1; }
+            '(' => { tokens.push((Token::LParen, start)); i += 1; }
+            ')' => { tokens.push((Token::RParen, start)); i += 1; }
+            ',' => { tokens.push((Token::Comma, start)); i += 1; }
+            '=' if chars.get(i + 1) == Some(&'=') => { tokens.push((Token::Eq, start)); i += 2; }
+            '!' if chars.get(i + 1) == Some(&'=') => { tokens.push((Token::Ne, start)); i += 2; }
+            '>' if chars.get(i + 1) == Some(&'=') => { tokens.push((Token::Ge, start)); i += 2; }
+            '<' if chars.get(i + 1) == Some(&'=') => { tokens.push((Token::Le, start)); i += 2; }
+            '>' => { tokens.push((Token::Gt, start)); i += 1; }
+            '<' => { tokens.push((Token::Lt, start)); i += 1; }
+            '"' => {
+                i += 1;
+                let mut s = String::new();
+                while i < chars.len() && chars[i] != '"' {
+                    s.push(chars[i]);
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(KaryakshamError::ParameterError(anyhow::anyhow!(
+                        "Unterminated string literal starting at position {} in expression '{}'",
+                        start, source
+                    )));
+                }
+                i += 1; // Consume the closing quote.
+                tokens.push((Token::Str(s), start));
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let value = text.parse::<f64>().map_err(|_| {
+                    KaryakshamError::ParameterError(anyhow::anyhow!(
+                        "Invalid number '{}' at position {} in expression '{}'",
+                        text, start, source
+                    ))
+                })?;
+                tokens.push((Token::Number(value), start));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                tokens.push((Token::Ident(text), start));
+            }
+            other => {
+                return Err(KaryakshamError::ParameterError(anyhow::anyhow!(
+                    "Unexpected character '{}' at position {} in expression '{}'",
+                    other, start, source
+                )));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    source: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn peek_pos(&self) -> usize {
+        self.tokens.get(self.pos).map(|(_, p)| *p).unwrap_or(self.source.len())
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).map(|(t, _)| t.clone());
+        self.pos += 1;
+        tok
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> KaryakshamError {
+        KaryakshamError::ParameterError(anyhow::anyhow!(
+            "{} at position {} in expression '{}'",
+            message, self.peek_pos(), self.source
+        ))
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr> {
+        let left = self.parse_additive()?;
+        if let Some(op) = self.match_comparison() {
+            let right = self.parse_additive()?;
+            return Ok(Expr::BinaryOp(Box::new(left), op, Box::new(right)));
+        }
+        Ok(left)
+    }
+
+    fn match_comparison(&mut self) -> Option<BinOp> {
+        let op = match self.peek()? {
+            Token::Eq => BinOp::Eq,
+            Token::Ne => BinOp::Ne,
+            Token::Gt => BinOp::Gt,
+            Token::Lt => BinOp::Lt,
+            Token::Ge => BinOp::Ge,
+            Token::Le => BinOp::Le,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(op)
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr> {
+        let mut left = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => BinOp::Add,
+                Some(Token::Minus) => BinOp::Sub,
+                _ => break,
+            };
+            self.pos += 1;
+            let right = self.parse_multiplicative()?;
+            left = Expr::BinaryOp(Box::new(left), op, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr> {
+        let mut left = self.parse_primary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => BinOp::Mul,
+                Some(Token::Slash) => BinOp::Div,
+                _ => break,
+            };
+            self.pos += 1;
+            let right = self.parse_primary()?;
+            left = Expr::BinaryOp(Box::new(left), op, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr> {
+        match self.advance() {
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::Str(s)) => Ok(Expr::Str(s)),
+            Some(Token::LParen) => {
+                let inner = self.parse_expr()?;
+                match self.advance() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err(self.error("Expected closing ')'")),
+                }
+            }
+            Some(Token::Ident(name)) if matches!(self.peek(), Some(Token::LParen)) => {
+                self.pos += 1; // Consume '('.
+                let mut args = Vec::new();
+                if !matches!(self.peek(), Some(Token::RParen)) {
+                    loop {
+                        args.push(self.parse_expr()?);
+                        if matches!(self.peek(), Some(Token::Comma)) {
+                            self.pos += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                match self.advance() {
+                    Some(Token::RParen) => {}
+                    _ => return Err(self.error("Expected closing ')' after function arguments")),
+                }
+
+                if name == "col" {
+                    return match args.as_slice() {
+                        [Expr::Str(column)] => Ok(Expr::Column(column.clone())),
+                        _ => Err(self.error("col(...) expects exactly one string literal argument")),
+                    };
+                }
+                Ok(Expr::Call(name, args))
+            }
+            Some(Token::Ident(name)) if name == "true" => Ok(Expr::Bool(true)),
+            Some(Token::Ident(name)) if name == "false" => Ok(Expr::Bool(false)),
+            // A bare identifier (not followed by `(`) is a column reference by name.
+            Some(Token::Ident(name)) => Ok(Expr::Column(name)),
+            _ => Err(self.error("Expected a value, column reference, or function call")),
+        }
+    }
+}
+
+/// Parses `source` into an [`Expr`] AST, failing with a `ParameterError` naming the offending
+/// token and its position rather than panicking.
+fn parse_expression(source: &str) -> Result<Expr> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser { tokens, pos: 0, source };
+    let expr = parser.parse_expr()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(parser.error("Unexpected trailing tokens after expression"));
+    }
+    Ok(expr)
+}
+
+/// Evaluates `expr` against `row`'s typed fields, producing the computed column's value.
+fn evaluate_expr(expr: &Expr, row: &Row) -> Result<serde_json::Value> {
+    match expr {
+        Expr::Number(n) => Ok(serde_json::Value::from(*n)),
+        Expr::Str(s) => Ok(serde_json::Value::from(s.clone())),
+        Expr::Bool(b) => Ok(serde_json::Value::from(*b)),
+        Expr::Column(name) => Ok(row.get(name).cloned().unwrap_or(serde_json::Value::Null)),
+        Expr::BinaryOp(lhs, op, rhs) => {
+            let left = evaluate_expr(lhs, row)?;
+            let right = evaluate_expr(rhs, row)?;
+            evaluate_binary_op(&left, *op, &right)
+        }
+        Expr::Call(name, args) => {
+            let values = args.iter().map(|a| evaluate_expr(a, row)).collect::<Result<Vec<_>>>()?;
+            evaluate_call(name, &values)
+        }
+    }
+}
+
+fn evaluate_binary_op(left: &serde_json::Value, op: BinOp, right: &serde_json::Value) -> Result<serde_json::Value> {
+    match op {
+        BinOp::Add => numeric_or_concat(left, right),
+        BinOp::Sub => numeric_binary(left, right, |a, b| a - b),
+        BinOp::Mul => numeric_binary(left, right, |a, b| a * b),
+        BinOp::Div => numeric_binary(left, right, |a, b| a / b),
+        BinOp::Eq => Ok(serde_json::Value::from(values_loosely_equal(left, right))),
+        BinOp::Ne => Ok(serde_json::Value::from(!values_loosely_equal(left, right))),
+        BinOp::Gt => numeric_comparison(left, right, |o| o == Ordering::Greater),
+        BinOp::Lt => numeric_comparison(left, right, |o| o == Ordering::Less),
+        BinOp::Ge => numeric_comparison(left, right, |o| o != Ordering::Less),
+        BinOp::Le => numeric_comparison(left, right, |o| o != Ordering::Greater),
+    }
+}
+
+/// `+` is numeric addition when both sides parse as numbers, and string concatenation otherwise
+/// (covering the "string concat" half of the request alongside arithmetic `+`).
+fn numeric_or_concat(left: &serde_json::Value, right: &serde_json::Value) -> Result<serde_json::Value> {
+    match (left.as_f64(), right.as_f64()) {
+        (Some(a), Some(b)) => Ok(serde_json::Value::from(a + b)),
+        _ => Ok(serde_json::Value::from(format!("{}{}", value_to_csv_field(left), value_to_csv_field(right)))),
+    }
+}
+
+fn numeric_binary(left: &serde_json::Value, right: &serde_json::Value, f: impl Fn(f64, f64) -> f64) -> Result<serde_json::Value> {
+    let a = numeric_operand(left)?;
+    let b = numeric_operand(right)?;
+    Ok(serde_json::Value::from(f(a, b)))
+}
+
+fn numeric_comparison(
+    left: &serde_json::Value,
+    right: &serde_json::Value,
+    matches_ordering: impl Fn(Ordering) -> bool,
+) -> Result<serde_json::Value> {
+    let a = numeric_operand(left)?;
+    let b = numeric_operand(right)?;
+    let ordering = a
+        .partial_cmp(&b)
+        .ok_or_else(|| KaryakshamError::ProcessingError(anyhow::anyhow!("Cannot compare NaN values")))?;
+    Ok(serde_json::Value::from(matches_ordering(ordering)))
+}
+
+fn numeric_operand(value: &serde_json::Value) -> Result<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| KaryakshamError::ProcessingError(anyhow::anyhow!("Expected a numeric value, got {:?}", value)))
+}
+
+fn values_loosely_equal(left: &serde_json::Value, right: &serde_json::Value) -> bool {
+    if let (Some(a), Some(b)) = (left.as_f64(), right.as_f64()) {
+        return a == b;
+    }
+    left == right
+}
+
+fn evaluate_call(name: &str, args: &[serde_json::Value]) -> Result<serde_json::Value> {
+    match name {
+        "upper" => Ok(serde_json::Value::from(arg_as_string(args, 0)?.to_uppercase())),
+        "lower" => Ok(serde_json::Value::from(arg_as_string(args, 0)?.to_lowercase())),
+        "len" => Ok(serde_json::Value::from(arg_as_string(args, 0)?.chars().count() as i64)),
+        "round" => Ok(serde_json::Value::from(numeric_operand(args.get(0).unwrap_or(&serde_json::Value::Null))?.round())),
+        "coalesce" => Ok(args.iter().find(|v| !v.is_null()).cloned().unwrap_or(serde_json::Value::Null)),
+        other => Err(KaryakshamError::ParameterError(anyhow::anyhow!("Unknown function '{}'", other))),
+    }
+}
+
+fn arg_as_string(args: &[serde_json::Value], index: usize) -> Result<String> {
+    args.get(index)
+        .map(value_to_csv_field)
+        .ok_or_else(|| KaryakshamError::ParameterError(anyhow::anyhow!("Missing argument {} in function call", index)))
 }
\ No newline at end of file
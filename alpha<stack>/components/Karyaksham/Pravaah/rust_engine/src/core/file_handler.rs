@@ -1,24 +1,76 @@
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+use std::time::Duration;
+
+use aws_config::imds::credentials::ImdsCredentialsProvider;
+use aws_config::environment::EnvironmentVariableCredentialsProvider;
+use aws_config::web_identity_token::WebIdentityTokenCredentialsProvider;
 use aws_sdk_s3::{
-    config::{self, Region},
-    primitives::ByteStream,
+    config::{self, Region, SharedCredentialsProvider},
+    presigning::PresigningConfig,
+    primitives::{ByteStream, DateTime},
+    types::{CompletedMultipartUpload, CompletedPart},
     Client,
 };
 use aws_smithy_http::endpoint::Endpoint;
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
 use tracing::{info, debug};
 use url::Url;
 
 use crate::utils::error::{KaryakshamError, Result};
 
+/// The on-disk/on-object encoding `DataProcessor` reads or writes a file as. Threaded through
+/// `ProcessingJobParams` from the Python-supplied job config, and inferred from a path's
+/// extension by `infer_file_format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileFormat {
+    Csv,
+    Parquet,
+}
+
+/// `DataProcessor` predates this module settling on the `ObjectStorageHandler` name; kept as an
+/// alias so its signatures don't need touching every time the handler is renamed.
+pub type ObjectStorageFileHandler = ObjectStorageHandler;
+
+/// S3 requires every part of a multipart upload except the last to be at least 5 MiB.
+const MIN_MULTIPART_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// S3 presigned URLs cannot be valid for more than 7 days.
+const MAX_PRESIGNED_EXPIRY: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
 /// Configuration for the object storage client.
 #[derive(Debug, Clone)]
 pub struct ObjectStorageConfig {
     pub endpoint_url: Option<String>,
     pub region: String,
-    pub access_key_id: String,
-    pub secret_access_key: String,
+    pub credentials: CredentialSource,
     pub bucket_name: String,
 }
 
+/// Where `ObjectStorageHandler::new` should source AWS credentials from. `Static` keeps literal,
+/// long-lived keys working for local MinIO; the other variants let the handler run under
+/// IAM roles / Kubernetes workload identity without embedding secrets anywhere.
+#[derive(Debug, Clone)]
+pub enum CredentialSource {
+    /// Literal access/secret keys, supplied directly (e.g. for local MinIO).
+    Static {
+        access_key_id: String,
+        secret_access_key: String,
+    },
+    /// Defers to the standard environment variables
+    /// (`AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY`/`AWS_SESSION_TOKEN`).
+    Environment,
+    /// EC2/ECS Instance Metadata Service credentials, for workloads running on AWS compute with
+    /// an attached IAM instance profile or task role.
+    InstanceMetadata,
+    /// Exchanges an OIDC token file (e.g. a mounted Kubernetes service account token) for
+    /// temporary STS credentials via `AssumeRoleWithWebIdentity`, for EKS/Kubernetes workload
+    /// identity deployments.
+    WebIdentity { role_arn: String, token_file: String },
+}
+
 /// A client for interacting with S3-compatible object storage.
 /// It wraps the AWS SDK S3 client and provides simplified methods for common operations.
 #[derive(Debug, Clone)]
@@ -28,6 +80,14 @@ pub struct ObjectStorageHandler {
 }
 
 impl ObjectStorageHandler {
+    /// Wraps an already-built S3 `Client` for a specific bucket, for callers (like
+    /// [`read_stream_from_s3`]/[`write_stream_to_s3`]) that already hold a client constructed
+    /// from the ambient AWS config and just need `ObjectStorageHandler`'s key-based helpers,
+    /// without re-resolving credentials through [`ObjectStorageHandler::new`].
+    pub fn from_client(client: Client, bucket_name: String) -> Self {
+        Self { client, bucket_name }
+    }
+
     /// Creates a new `ObjectStorageHandler` instance.
     ///
     /// Initializes the AWS SDK S3 client with provided configuration.
@@ -42,16 +102,34 @@ impl ObjectStorageHandler {
     pub async fn new(config: ObjectStorageConfig) -> Result<Self> {
         let mut sdk_config_builder = config::SdkConfig::builder();
 
-        // Set credentials using static provider
-        sdk_config_builder = sdk_config_builder.credentials_provider(
-            aws_sdk_s3::config::Credentials::new(
-                &config.access_key_id,
-                &config.secret_access_key,
-                None, // Session token
-                None, // Expiration
-                "karyaksham_static_credentials", // Provider name for tracing
-            ),
-        );
+        // Resolve the configured `CredentialSource` into a single `SharedCredentialsProvider` so
+        // the rest of this function doesn't need to care which concrete provider backs it.
+        let credentials_provider: SharedCredentialsProvider = match &config.credentials {
+            CredentialSource::Static { access_key_id, secret_access_key } => {
+                SharedCredentialsProvider::new(aws_sdk_s3::config::Credentials::new(
+                    access_key_id,
+                    secret_access_key,
+                    None, // Session token
+                    None, // Expiration
+                    "karyaksham_static_credentials", // Provider name for tracing
+                ))
+            }
+            CredentialSource::Environment => {
+                SharedCredentialsProvider::new(EnvironmentVariableCredentialsProvider::new())
+            }
+            CredentialSource::InstanceMetadata => {
+                SharedCredentialsProvider::new(ImdsCredentialsProvider::builder().build())
+            }
+            CredentialSource::WebIdentity { role_arn, token_file } => {
+                SharedCredentialsProvider::new(
+                    WebIdentityTokenCredentialsProvider::builder()
+                        .role_arn(role_arn.clone())
+                        .web_identity_token_file(token_file.clone())
+                        .build(),
+                )
+            }
+        };
+        sdk_config_builder = sdk_config_builder.credentials_provider(credentials_provider);
 
         // Set AWS region
         sdk_config_builder = sdk_config_builder.region(Region::new(config.region.clone()));
@@ -138,6 +216,162 @@ impl ObjectStorageHandler {
         Ok(())
     }
 
+    /// Uploads a potentially multi-gigabyte object to S3 via a multipart upload, reading
+    /// `reader` in `part_size`-byte chunks (clamped up to the S3-mandated 5 MiB minimum for
+    /// non-final parts) so memory use stays bounded no matter how large the source is.
+    ///
+    /// Initiates the upload with `create_multipart_upload` to obtain an `upload_id`, then streams
+    /// `reader` through `upload_part` calls with incrementing, 1-based part numbers, collecting
+    /// each part's returned ETag into a `CompletedPart`. Once `reader` is exhausted, the parts
+    /// (sorted by part number) are assembled into a `CompletedMultipartUpload` and finalized via
+    /// `complete_multipart_upload`. If any step fails partway through, `abort_multipart_upload` is
+    /// called to avoid leaving orphaned parts (and their storage cost) behind before the original
+    /// error is returned.
+    ///
+    /// # Arguments
+    /// * `key` - The object key (path) within the bucket where the data will be stored.
+    /// * `reader` - An async reader over the source data; read and uploaded incrementally.
+    /// * `part_size` - The target size in bytes for each part; clamped up to the 5 MiB minimum.
+    ///
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or `KaryakshamError` on failure (e.g., network
+    /// issues, S3 errors, or a read error on `reader`).
+    pub async fn upload_file_multipart<R>(&self, key: &str, mut reader: R, part_size: usize) -> Result<()>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let part_size = part_size.max(MIN_MULTIPART_PART_SIZE);
+
+        info!(
+            "Starting multipart upload to s3://{}/{} (part size {} bytes)",
+            self.bucket_name, key, part_size
+        );
+
+        let create_output = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| {
+                KaryakshamError::IoError(format!("Failed to initiate multipart upload for {}: {}", key, e))
+            })?;
+
+        let upload_id = create_output
+            .upload_id()
+            .ok_or_else(|| KaryakshamError::IoError(format!("S3 did not return an upload_id for {}", key)))?
+            .to_string();
+
+        let result = match self.upload_parts(key, &upload_id, &mut reader, part_size).await {
+            Ok(mut completed_parts) => {
+                completed_parts.sort_by_key(|part| part.part_number().unwrap_or_default());
+
+                let completed_upload = CompletedMultipartUpload::builder()
+                    .set_parts(Some(completed_parts))
+                    .build();
+
+                self.client
+                    .complete_multipart_upload()
+                    .bucket(&self.bucket_name)
+                    .key(key)
+                    .upload_id(&upload_id)
+                    .multipart_upload(completed_upload)
+                    .send()
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| {
+                        KaryakshamError::IoError(format!("Failed to complete multipart upload for {}: {}", key, e))
+                    })
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(()) => {
+                info!("Successfully completed multipart upload to s3://{}/{}", self.bucket_name, key);
+                Ok(())
+            }
+            Err(e) => {
+                debug!("Aborting multipart upload {} for {} after error: {}", upload_id, key, e);
+                let _ = self
+                    .client
+                    .abort_multipart_upload()
+                    .bucket(&self.bucket_name)
+                    .key(key)
+                    .upload_id(&upload_id)
+                    .send()
+                    .await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Reads `reader` in `part_size` chunks, uploading each as a part of the in-progress
+    /// multipart upload `upload_id` and collecting the resulting `CompletedPart` entries.
+    async fn upload_parts<R>(
+        &self,
+        key: &str,
+        upload_id: &str,
+        reader: &mut R,
+        part_size: usize,
+    ) -> Result<Vec<CompletedPart>>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut completed_parts = Vec::new();
+        let mut part_number: i32 = 1;
+
+        loop {
+            let mut buffer = vec![0u8; part_size];
+            let mut filled = 0;
+
+            while filled < buffer.len() {
+                let read = reader.read(&mut buffer[filled..]).await.map_err(|e| {
+                    KaryakshamError::IoError(format!("Failed to read source data for {}: {}", key, e))
+                })?;
+                if read == 0 {
+                    break;
+                }
+                filled += read;
+            }
+
+            if filled == 0 {
+                break;
+            }
+            buffer.truncate(filled);
+
+            let upload_part_output = self
+                .client
+                .upload_part()
+                .bucket(&self.bucket_name)
+                .key(key)
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .body(ByteStream::from(buffer))
+                .send()
+                .await
+                .map_err(|e| {
+                    KaryakshamError::IoError(format!("Failed to upload part {} for {}: {}", part_number, key, e))
+                })?;
+
+            let e_tag = upload_part_output.e_tag().ok_or_else(|| {
+                KaryakshamError::IoError(format!("S3 did not return an ETag for part {} of {}", part_number, key))
+            })?;
+
+            completed_parts.push(
+                CompletedPart::builder()
+                    .part_number(part_number)
+                    .e_tag(e_tag)
+                    .build(),
+            );
+
+            part_number += 1;
+        }
+
+        Ok(completed_parts)
+    }
+
     /// Deletes a file from the configured S3 bucket.
     ///
     /// # Arguments
@@ -160,6 +394,88 @@ impl ObjectStorageHandler {
         Ok(())
     }
 
+    /// Generates a time-limited, presigned GET URL for `key`, so a caller (e.g. the Python API's
+    /// frontend) can download the object directly from S3/MinIO without the bytes passing through
+    /// this service.
+    ///
+    /// `response_content_disposition`, if given, is applied to the presigned request so the
+    /// resulting download response carries that `Content-Disposition` header (e.g.
+    /// `attachment; filename="report.csv"`), letting a caller force a browser download with a
+    /// chosen filename.
+    ///
+    /// # Arguments
+    /// * `key` - The object key (path) within the bucket to generate a download URL for.
+    /// * `expires_in` - How long the URL stays valid; must not exceed S3's 7-day maximum.
+    /// * `response_content_disposition` - An optional `Content-Disposition` override for the GET.
+    ///
+    /// # Returns
+    /// A `Result` containing the presigned URL as a `String`, or `KaryakshamError::InputError` if
+    /// `expires_in` is out of range, or `KaryakshamError::IoError` if presigning fails.
+    pub async fn presign_get(
+        &self,
+        key: &str,
+        expires_in: Duration,
+        response_content_disposition: Option<&str>,
+    ) -> Result<String> {
+        Self::validate_presign_expiry(expires_in)?;
+
+        let presigning_config = PresigningConfig::expires_in(expires_in)
+            .map_err(|e| KaryakshamError::InputError(format!("Invalid presigned URL expiry: {}", e)))?;
+
+        let mut request = self.client.get_object().bucket(&self.bucket_name).key(key);
+        if let Some(disposition) = response_content_disposition {
+            request = request.response_content_disposition(disposition);
+        }
+
+        let presigned = request
+            .presigned(presigning_config)
+            .await
+            .map_err(|e| KaryakshamError::IoError(format!("Failed to presign GET for {}: {}", key, e)))?;
+
+        info!("Generated presigned GET URL for s3://{}/{} (expires in {:?})", self.bucket_name, key, expires_in);
+        Ok(presigned.uri().to_string())
+    }
+
+    /// Generates a time-limited, presigned PUT URL for `key`, so a caller can upload directly to
+    /// S3/MinIO without streaming the bytes through this service.
+    ///
+    /// # Arguments
+    /// * `key` - The object key (path) within the bucket to generate an upload URL for.
+    /// * `expires_in` - How long the URL stays valid; must not exceed S3's 7-day maximum.
+    ///
+    /// # Returns
+    /// A `Result` containing the presigned URL as a `String`, or `KaryakshamError::InputError` if
+    /// `expires_in` is out of range, or `KaryakshamError::IoError` if presigning fails.
+    pub async fn presign_put(&self, key: &str, expires_in: Duration) -> Result<String> {
+        Self::validate_presign_expiry(expires_in)?;
+
+        let presigning_config = PresigningConfig::expires_in(expires_in)
+            .map_err(|e| KaryakshamError::InputError(format!("Invalid presigned URL expiry: {}", e)))?;
+
+        let presigned = self
+            .client
+            .put_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .presigned(presigning_config)
+            .await
+            .map_err(|e| KaryakshamError::IoError(format!("Failed to presign PUT for {}: {}", key, e)))?;
+
+        info!("Generated presigned PUT URL for s3://{}/{} (expires in {:?})", self.bucket_name, key, expires_in);
+        Ok(presigned.uri().to_string())
+    }
+
+    /// Rejects `expires_in` values beyond S3's 7-day maximum for presigned URLs.
+    fn validate_presign_expiry(expires_in: Duration) -> Result<()> {
+        if expires_in > MAX_PRESIGNED_EXPIRY {
+            return Err(KaryakshamError::InputError(format!(
+                "presigned URL expiry {:?} exceeds S3's 7-day maximum of {:?}",
+                expires_in, MAX_PRESIGNED_EXPIRY
+            )));
+        }
+        Ok(())
+    }
+
     /// Parses a full S3 path string (e.g., "s3://my-bucket/path/to/file.csv")
     /// into its constituent bucket name and object key.
     ///
@@ -192,4 +508,409 @@ impl ObjectStorageHandler {
 
         Ok((bucket, key))
     }
+
+    /// Copies `src_key` to `dst_key` entirely on the server side via `copy_object`, avoiding a
+    /// download/re-upload round trip through this service. `src_bucket` allows copying from a
+    /// different bucket than the one this handler is configured for (e.g. promoting a result from
+    /// a staging bucket to a published bucket); `None` copies within this handler's own bucket.
+    ///
+    /// # Arguments
+    /// * `src_key` - The key of the object to copy.
+    /// * `dst_key` - The key the copy should be written to, in this handler's bucket.
+    /// * `src_bucket` - The bucket to copy from; `None` means this handler's own bucket.
+    ///
+    /// # Errors
+    /// Returns `KaryakshamError::IoError` if the underlying `copy_object` call fails.
+    pub async fn copy_object(&self, src_key: &str, dst_key: &str, src_bucket: Option<&str>) -> Result<()> {
+        let source_bucket = src_bucket.unwrap_or(&self.bucket_name);
+        let copy_source = format!("{}/{}", source_bucket, percent_encode_copy_source_key(src_key));
+
+        info!("Copying s3://{}/{} to s3://{}/{}", source_bucket, src_key, self.bucket_name, dst_key);
+
+        self.client
+            .copy_object()
+            .bucket(&self.bucket_name)
+            .key(dst_key)
+            .copy_source(copy_source)
+            .send()
+            .await
+            .map_err(|e| {
+                KaryakshamError::IoError(format!(
+                    "Failed to copy s3://{}/{} to s3://{}/{}: {}",
+                    source_bucket, src_key, self.bucket_name, dst_key, e
+                ))
+            })?;
+
+        info!("Successfully copied s3://{}/{} to s3://{}/{}", source_bucket, src_key, self.bucket_name, dst_key);
+        Ok(())
+    }
+
+    /// Moves `src_key` to `dst_key` via a server-side `copy_object` followed by deleting the
+    /// source, so the object is renamed/promoted without ever leaving S3. `src_bucket` behaves as
+    /// in `copy_object`.
+    ///
+    /// # Errors
+    /// Returns `KaryakshamError::IoError` if either the copy or the subsequent delete fails; if
+    /// the delete fails the copy has already succeeded, so `dst_key` exists but `src_key` was not
+    /// removed.
+    pub async fn move_object(&self, src_key: &str, dst_key: &str, src_bucket: Option<&str>) -> Result<()> {
+        self.copy_object(src_key, dst_key, src_bucket).await?;
+
+        let source_bucket = src_bucket.unwrap_or(&self.bucket_name).to_string();
+        self.client
+            .delete_object()
+            .bucket(&source_bucket)
+            .key(src_key)
+            .send()
+            .await
+            .map_err(|e| {
+                KaryakshamError::IoError(format!(
+                    "Copied s3://{}/{} to s3://{}/{} but failed to delete the source: {}",
+                    source_bucket, src_key, self.bucket_name, dst_key, e
+                ))
+            })?;
+
+        info!("Successfully moved s3://{}/{} to s3://{}/{}", source_bucket, src_key, self.bucket_name, dst_key);
+        Ok(())
+    }
+
+    /// Lists every object under `prefix` (or the whole bucket if `None`), looping over
+    /// `list_objects_v2` until its `is_truncated` flag clears. Buffers every page in memory, so
+    /// for buckets that may hold millions of objects prefer `list_objects_stream` instead.
+    ///
+    /// # Arguments
+    /// * `prefix` - Only objects whose key starts with this are returned; `None` lists the bucket.
+    ///
+    /// # Returns
+    /// A `Result` containing every matching `ObjectMeta`, or `KaryakshamError::IoError` if a page
+    /// request fails.
+    pub async fn list_objects(&self, prefix: Option<&str>) -> Result<Vec<ObjectMeta>> {
+        let mut stream = self.list_objects_stream(prefix);
+        let mut all_objects = Vec::new();
+        while let Some(page) = stream.next_page().await? {
+            all_objects.extend(page);
+        }
+        Ok(all_objects)
+    }
+
+    /// Returns a page-at-a-time cursor over `list_objects_v2` results for `prefix` (or the whole
+    /// bucket if `None`); each `ObjectListingStream::next_page` call issues a single request, so
+    /// memory use stays bounded to one page no matter how many objects the bucket holds. This is
+    /// what job-orchestration code should use to discover partition/result files under a prefix
+    /// like `results/<job-id>/` without tracking every key externally.
+    pub fn list_objects_stream(&self, prefix: Option<&str>) -> ObjectListingStream {
+        ObjectListingStream {
+            client: self.client.clone(),
+            bucket_name: self.bucket_name.clone(),
+            prefix: prefix.map(|p| p.to_string()),
+            continuation_token: None,
+            done: false,
+        }
+    }
+
+    /// Opens `key` for reading as an `AsyncRead`-compatible stream; `DataProcessor`'s name for
+    /// [`ObjectStorageHandler::download_file`].
+    pub async fn read_file(&self, key: &str) -> Result<ByteStream> {
+        self.download_file(key).await
+    }
+
+    /// Opens `key` for writing, returning an `AsyncWrite` sink that uploads its full contents as
+    /// a single `PUT` once closed (see [`S3UploadSink`]).
+    pub async fn create_file(&self, key: &str) -> Result<S3UploadSink> {
+        Ok(S3UploadSink::new(self.client.clone(), self.bucket_name.clone(), key.to_string()))
+    }
+}
+
+/// Metadata for a single object, collected from a `list_objects_v2` page.
+#[derive(Debug, Clone)]
+pub struct ObjectMeta {
+    pub key: String,
+    pub size: i64,
+    pub last_modified: Option<DateTime>,
+    pub etag: Option<String>,
+}
+
+/// A page-at-a-time cursor over `list_objects_v2`, re-issuing the request with
+/// `next_continuation_token` until the bucket (or prefix) is exhausted. Construct via
+/// `ObjectStorageHandler::list_objects_stream`.
+pub struct ObjectListingStream {
+    client: Client,
+    bucket_name: String,
+    prefix: Option<String>,
+    continuation_token: Option<String>,
+    done: bool,
+}
+
+impl ObjectListingStream {
+    /// Fetches the next page of objects, or `None` once the listing is exhausted.
+    ///
+    /// # Errors
+    /// Returns `KaryakshamError::IoError` if the `list_objects_v2` request fails.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<ObjectMeta>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let mut request = self.client.list_objects_v2().bucket(&self.bucket_name);
+        if let Some(prefix) = &self.prefix {
+            request = request.prefix(prefix);
+        }
+        if let Some(token) = &self.continuation_token {
+            request = request.continuation_token(token);
+        }
+
+        let output = request.send().await.map_err(|e| {
+            KaryakshamError::IoError(format!("Failed to list objects in {}: {}", self.bucket_name, e))
+        })?;
+
+        let page: Vec<ObjectMeta> = output
+            .contents()
+            .iter()
+            .map(|object| ObjectMeta {
+                key: object.key().unwrap_or_default().to_string(),
+                size: object.size().unwrap_or_default(),
+                last_modified: object.last_modified().cloned(),
+                etag: object.e_tag().map(|s| s.to_string()),
+            })
+            .collect();
+
+        if output.is_truncated().unwrap_or(false) {
+            self.continuation_token = output.next_continuation_token().map(|s| s.to_string());
+        } else {
+            self.done = true;
+        }
+
+        Ok(Some(page))
+    }
+}
+
+/// An `AsyncWrite` sink that buffers everything written to it in memory, then uploads the whole
+/// buffer as a single `PUT` object once the sink is shut down. Returned by
+/// [`ObjectStorageHandler::create_file`] for `DataProcessor`'s Arrow/CSV/Parquet writers, which
+/// need a generic `AsyncWrite` target; unlike a download, a multipart upload body can't be driven
+/// incrementally through that interface, so this mirrors the whole-buffer-then-flush-once
+/// pattern `DataProcessor` already uses for CSV serialization.
+pub struct S3UploadSink {
+    client: Client,
+    bucket_name: String,
+    key: String,
+    buffer: Vec<u8>,
+    upload: Option<BoxFuture<'static, Result<()>>>,
+}
+
+impl S3UploadSink {
+    fn new(client: Client, bucket_name: String, key: String) -> Self {
+        Self {
+            client,
+            bucket_name,
+            key,
+            buffer: Vec::new(),
+            upload: None,
+        }
+    }
+}
+
+impl AsyncWrite for S3UploadSink {
+    fn poll_write(mut self: Pin<&mut Self>, _cx: &mut TaskContext<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        self.buffer.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut TaskContext<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<std::io::Result<()>> {
+        if self.upload.is_none() {
+            let client = self.client.clone();
+            let bucket_name = self.bucket_name.clone();
+            let key = self.key.clone();
+            let data = std::mem::take(&mut self.buffer);
+            self.upload = Some(Box::pin(async move {
+                client
+                    .put_object()
+                    .bucket(bucket_name)
+                    .key(&key)
+                    .body(ByteStream::from(data))
+                    .send()
+                    .await
+                    .map_err(|e| KaryakshamError::IoError(format!("Failed to upload {}: {}", key, e)))?;
+                Ok(())
+            }));
+        }
+
+        match self.upload.as_mut().unwrap().as_mut().poll(cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(())),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Downloads `s3_path` (a full `s3://bucket/key` URI) via `client`, for
+/// `run_csv_processing_pipeline`'s read step. Builds a one-off [`ObjectStorageHandler`] around
+/// `client` rather than going through [`ObjectStorageHandler::new`], since the caller already
+/// resolved credentials/region once for the whole pipeline.
+pub async fn read_stream_from_s3(client: &Client, s3_path: &str) -> Result<ByteStream> {
+    let (bucket, key) = ObjectStorageHandler::parse_s3_path(s3_path)?;
+    ObjectStorageHandler::from_client(client.clone(), bucket).download_file(&key).await
+}
+
+/// Uploads `data` to `s3_path` (a full `s3://bucket/key` URI) via `client`, for
+/// `run_csv_processing_pipeline`'s write step. See [`read_stream_from_s3`] for why it builds its
+/// own handler around `client` rather than taking one directly.
+pub async fn write_stream_to_s3(client: &Client, s3_path: &str, data: ByteStream) -> Result<()> {
+    let (bucket, key) = ObjectStorageHandler::parse_s3_path(s3_path)?;
+    ObjectStorageHandler::from_client(client.clone(), bucket).upload_file(&key, data).await
+}
+
+/// A seekable, `std::io::Read`-compatible view over a single S3 object, fetching only the byte
+/// ranges it's asked for via `get_object` with a `Range` header. Lets callers for columnar
+/// formats (e.g. a Parquet reader that seeks to the footer before the row groups) work with just
+/// the slices of a large object they need instead of downloading it whole.
+///
+/// Each `Read`/`Seek` call bridges to the handler's async S3 client via `block_on`, so an
+/// `S3RangeReader` must only be used from a thread where blocking is acceptable (e.g. inside
+/// `spawn_blocking`), the same constraint `std::io::Read` implementations over network storage
+/// generally carry.
+pub struct S3RangeReader {
+    client: Client,
+    bucket_name: String,
+    key: String,
+    content_length: u64,
+    cursor: u64,
+    runtime: tokio::runtime::Handle,
+}
+
+impl S3RangeReader {
+    /// Opens `key` in `handler`'s bucket, issuing a `head_object` to learn and cache its
+    /// `content_length` up front so `Seek::End` and EOF checks never need a network round trip.
+    ///
+    /// # Errors
+    /// Returns `KaryakshamError::IoError` if `head_object` fails or doesn't report a length.
+    pub async fn open(handler: &ObjectStorageHandler, key: &str) -> Result<Self> {
+        info!("Opening S3RangeReader for s3://{}/{}", handler.bucket_name, key);
+
+        let head_output = handler
+            .client
+            .head_object()
+            .bucket(&handler.bucket_name)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| KaryakshamError::IoError(format!("Failed to head_object {}: {}", key, e)))?;
+
+        let content_length = head_output
+            .content_length()
+            .filter(|len| *len >= 0)
+            .ok_or_else(|| KaryakshamError::IoError(format!("S3 did not report a content length for {}", key)))?
+            as u64;
+
+        Ok(Self {
+            client: handler.client.clone(),
+            bucket_name: handler.bucket_name.clone(),
+            key: key.to_string(),
+            content_length,
+            cursor: 0,
+            runtime: tokio::runtime::Handle::current(),
+        })
+    }
+
+    /// The total size of the underlying object, cached from the `head_object` at construction.
+    pub fn content_length(&self) -> u64 {
+        self.content_length
+    }
+
+    /// Fetches the inclusive byte range `[start, end_inclusive]` via `get_object`.
+    fn read_range(&self, start: u64, end_inclusive: u64) -> Result<Vec<u8>> {
+        if start > end_inclusive {
+            return Err(KaryakshamError::InvalidRange(format!(
+                "range start {} is past end {}",
+                start, end_inclusive
+            )));
+        }
+
+        let range_header = format!("bytes={}-{}", start, end_inclusive);
+        self.runtime.block_on(async {
+            let output = self
+                .client
+                .get_object()
+                .bucket(&self.bucket_name)
+                .key(&self.key)
+                .range(range_header)
+                .send()
+                .await
+                .map_err(|e| KaryakshamError::IoError(format!("Failed to read range of {}: {}", self.key, e)))?;
+
+            output
+                .body
+                .collect()
+                .await
+                .map(|data| data.into_bytes().to_vec())
+                .map_err(|e| KaryakshamError::IoError(format!("Failed to read range body of {}: {}", self.key, e)))
+        })
+    }
+}
+
+impl std::io::Read for S3RangeReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() || self.cursor >= self.content_length {
+            return Ok(0);
+        }
+
+        let start = self.cursor;
+        let end_inclusive = (start + buf.len() as u64 - 1).min(self.content_length - 1);
+
+        let data = self
+            .read_range(start, end_inclusive)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+
+        let n = data.len().min(buf.len());
+        buf[..n].copy_from_slice(&data[..n]);
+        self.cursor += n as u64;
+        Ok(n)
+    }
+}
+
+impl std::io::Seek for S3RangeReader {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        let (base, offset) = match pos {
+            std::io::SeekFrom::Start(offset) => (0i64, offset as i64),
+            std::io::SeekFrom::End(offset) => (self.content_length as i64, offset),
+            std::io::SeekFrom::Current(offset) => (self.cursor as i64, offset),
+        };
+
+        let new_cursor = base.checked_add(offset).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                KaryakshamError::InvalidRange(format!("seek overflowed from base {} by offset {}", base, offset))
+                    .to_string(),
+            )
+        })?;
+
+        if new_cursor < 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                KaryakshamError::InvalidRange(format!("seek to negative offset {}", new_cursor)).to_string(),
+            ));
+        }
+
+        self.cursor = (new_cursor as u64).min(self.content_length);
+        Ok(self.cursor)
+    }
+}
+
+/// Percent-encodes `key` for use as the key portion of an S3 `x-amz-copy-source` header, per
+/// S3's requirement that the source key (though not the `/` bucket/key separator) be URL-encoded.
+fn percent_encode_copy_source_key(key: &str) -> String {
+    let mut encoded = String::with_capacity(key.len());
+    for byte in key.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
 }
\ No newline at end of file
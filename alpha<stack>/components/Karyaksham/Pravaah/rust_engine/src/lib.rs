@@ -1,6 +1,10 @@
 use pyo3::prelude::*;
 use pyo3::exceptions::PyException;
 use pyo3::create_exception;
+// `process_csv_file_async` below needs the `tokio` backend of `pyo3-asyncio` added to
+// Cargo.toml (`pyo3-asyncio = { version = "0.20", features = ["tokio-runtime"] }`).
+// The shared runtime below needs `once_cell` added to Cargo.toml.
+use once_cell::sync::OnceCell;
 use tokio::runtime::Runtime;
 use serde::Deserialize;
 use anyhow::Context;
@@ -12,18 +16,89 @@ mod utils;
 // Use specific items from internal modules
 use core::data_processor;
 use core::file_handler;
-use utils::error::{KaryakshamError, ResultExt}; // Assuming ResultExt for `context()`
+use utils::error::KaryakshamError;
 
-// Define a custom Python exception type for errors originating from the Rust engine.
-// This allows for more specific error handling on the Python side.
+/// The single multithreaded Tokio runtime shared by every blocking entry point in this module.
+/// Built lazily on first use (or eagerly via [`configure_runtime`]) rather than once per call, so
+/// a busy Celery worker isn't paying thread/epoll setup-and-teardown cost on every job.
+static RUNTIME: OnceCell<Runtime> = OnceCell::new();
+
+/// Returns the shared runtime, building it with a default worker count on first use.
+fn shared_runtime() -> Result<&'static Runtime, KaryakshamError> {
+    RUNTIME
+        .get_or_try_init(|| build_runtime(default_worker_threads()))
+        .context("Failed to initialize the shared Tokio runtime.")
+        .map_err(KaryakshamError::RuntimeError)
+}
+
+fn default_worker_threads() -> usize {
+    std::env::var("KARYAKSHAM_RUNTIME_WORKER_THREADS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4))
+}
+
+fn build_runtime(worker_threads: usize) -> std::io::Result<Runtime> {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(worker_threads)
+        .enable_all()
+        .build()
+}
+
+/// Tunes the worker-thread count of the shared Tokio runtime used by [`process_csv_file`] and
+/// [`process_csv_file_async`]. Must be called before the runtime is first used (i.e. before the
+/// first processing call) — once the shared runtime has been built, its thread pool is fixed for
+/// the lifetime of the process.
+#[pyfunction]
+fn configure_runtime(worker_threads: usize) -> PyResult<()> {
+    let rt = build_runtime(worker_threads)
+        .context("Failed to build the configured Tokio runtime.")
+        .map_err(KaryakshamError::RuntimeError)?;
+
+    RUNTIME.set(rt).map_err(|_| {
+        PyErr::from(KaryakshamError::RuntimeError(anyhow::anyhow!(
+            "configure_runtime() must be called before the shared runtime is first used"
+        )))
+    })
+}
+
+// Define a Python exception hierarchy for errors originating from the Rust engine, so Python
+// callers can `except KaryakshamIoError` (retry-worthy) separately from
+// `except KaryakshamParameterError` (caller's bug) instead of string-matching one flat exception.
 create_exception!(karyaksham_rust_engine, KaryakshamRustEngineError, PyException);
+create_exception!(karyaksham_rust_engine, KaryakshamIoError, KaryakshamRustEngineError);
+create_exception!(karyaksham_rust_engine, KaryakshamParameterError, KaryakshamRustEngineError);
+create_exception!(karyaksham_rust_engine, KaryakshamProcessingError, KaryakshamRustEngineError);
+create_exception!(karyaksham_rust_engine, KaryakshamRuntimeError, KaryakshamRustEngineError);
 
-// Implement conversion from our custom Rust error type (`KaryakshamError`) to PyErr.
-// This enables automatic error propagation from Rust functions to Python exceptions
-// when a `PyResult` is returned.
+// Implement conversion from our custom Rust error type (`KaryakshamError`) to PyErr, raising the
+// exception subclass matching the variant rather than the flat base class.
 impl From<KaryakshamError> for PyErr {
     fn from(err: KaryakshamError) -> PyErr {
-        KaryakshamRustEngineError::new_err(format!("{}", err))
+        let message = err.to_string();
+        let py_err = match &err {
+            KaryakshamError::IoError(_) => KaryakshamIoError::new_err(message),
+            KaryakshamError::ParameterError(_) => KaryakshamParameterError::new_err(message),
+            KaryakshamError::ProcessingError(_) => KaryakshamProcessingError::new_err(message),
+            KaryakshamError::RuntimeError(_) => KaryakshamRuntimeError::new_err(message),
+        };
+
+        // Preserve the `.context(...)` chain built up around the originating `anyhow::Error` as
+        // the Python exception's `__cause__`, so a traceback (or `except ... as e: e.__cause__`)
+        // shows every layer, not just `KaryakshamError`'s own top-level `Display` message.
+        //
+        // Note: `KaryakshamError`'s variants don't carry structured fields like an S3 path or an
+        // error code (see `utils/error.rs`), so unlike the request's `.s3_path`/`.error_code`
+        // suggestion, there's nothing further to expose here via `#[pyo3(get)]` yet.
+        let anyhow_source = match &err {
+            KaryakshamError::IoError(e)
+            | KaryakshamError::ParameterError(e)
+            | KaryakshamError::ProcessingError(e)
+            | KaryakshamError::RuntimeError(e) => e,
+        };
+        Python::with_gil(|py| py_err.set_cause(py, Some(PyException::new_err(format!("{:#}", anyhow_source)))));
+
+        py_err
     }
 }
 
@@ -53,6 +128,25 @@ pub enum FilterValue {
     // Extend with other types as necessary (e.g., Array for 'in' operator)
 }
 
+impl FilterValue {
+    /// Returns the value as a string, for the operators (`contains`, `starts_with`, ...) that only
+    /// make sense against string columns.
+    pub fn as_string(&self) -> Option<&str> {
+        match self {
+            FilterValue::String(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as `f64`, for the numeric comparison operators (`gt`, `lt`, ...).
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            FilterValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct FilterCondition {
     pub column: String,
@@ -114,63 +208,107 @@ pub struct Aggregation {
 /// A `PyResult` containing the `s3_output_path` string on successful completion,
 /// or a `PyErr` if any error occurs during the process (e.g., network issues, parsing errors,
 /// or processing failures).
+///
+/// `progress_callback`, if given, is invoked periodically (after parsing, after filtering,
+/// after each transformation, and before writing the output) with a dict
+/// `{rows_processed, bytes_read, percent}`. The GIL is only held for the duration of each such
+/// call — the rest of the pipeline runs with it released via `allow_threads` below. If the
+/// callback returns `False` or raises, that's treated as a cooperative cancel signal: the
+/// pipeline aborts with a `KaryakshamProcessingError` rather than running to completion.
 #[pyfunction]
+#[pyo3(signature = (s3_input_path, s3_output_path, processing_params_json, progress_callback = None))]
 fn process_csv_file(
-    _py: Python, // Renamed to _py to indicate it's unused in this specific function, per Clippy lint
+    py: Python,
     s3_input_path: String,
     s3_output_path: String,
     processing_params_json: String,
+    progress_callback: Option<PyObject>,
 ) -> PyResult<String> {
+    let rt = shared_runtime()?;
+
+    // Release the GIL for the duration of the blocking call, so other Python threads (e.g. a
+    // Celery worker's heartbeat) keep running while Rust does S3 I/O and CPU-bound processing.
+    py.allow_threads(|| {
+        rt.block_on(run_csv_processing_pipeline(
+            s3_input_path,
+            s3_output_path,
+            processing_params_json,
+            progress_callback,
+        ))
+    })
+    .map_err(PyErr::from) // Convert the error from the async block into a PyErr
+}
+
+/// Async-coroutine counterpart to [`process_csv_file`] for callers running inside an existing
+/// `asyncio` event loop (FastAPI handlers, etc.) where blocking the calling thread on `block_on`
+/// would stall the whole loop. Returns a Python awaitable backed by the same pipeline, via
+/// `pyo3-asyncio`'s Tokio integration, so many jobs can be scheduled concurrently instead of
+/// being serialized behind one OS thread per call. Accepts the same `progress_callback` as
+/// [`process_csv_file`].
+#[pyfunction]
+#[pyo3(signature = (s3_input_path, s3_output_path, processing_params_json, progress_callback = None))]
+fn process_csv_file_async(
+    py: Python,
+    s3_input_path: String,
+    s3_output_path: String,
+    processing_params_json: String,
+    progress_callback: Option<PyObject>,
+) -> PyResult<&PyAny> {
+    pyo3_asyncio::tokio::future_into_py(py, async move {
+        run_csv_processing_pipeline(s3_input_path, s3_output_path, processing_params_json, progress_callback)
+            .await
+            .map_err(PyErr::from)
+    })
+}
+
+/// The shared S3 read -> process -> write pipeline behind both [`process_csv_file`] and
+/// [`process_csv_file_async`], so the sync and coroutine entry points can't drift apart.
+async fn run_csv_processing_pipeline(
+    s3_input_path: String,
+    s3_output_path: String,
+    processing_params_json: String,
+    progress_callback: Option<PyObject>,
+) -> Result<String, KaryakshamError> {
     // Deserialize the JSON parameters into our Rust struct.
     // This allows Rust to work with strongly-typed configurations.
     let params: CsvProcessingParams = serde_json::from_str(&processing_params_json)
         .context("Failed to deserialize processing parameters JSON. Ensure JSON format matches expected schema.")
         .map_err(KaryakshamError::ParameterError)?; // Convert to our custom error type
 
-    // Create a Tokio runtime for executing asynchronous operations.
-    // `block_on` will block the current OS thread until all tasks within the async block
-    // complete. This is acceptable for a Celery worker, which is designed to handle
-    // long-running tasks.
-    let rt = Runtime::new()
-        .context("Failed to create Tokio runtime. This is critical for async operations.")
-        .map_err(KaryakshamError::RuntimeError)?;
-
-    rt.block_on(async {
-        // Load AWS SDK configuration (credentials, region, endpoint).
-        // This leverages standard AWS environment variables or config files.
-        let config = aws_config::load_from_env().await;
-        let s3_client = aws_sdk_s3::Client::new(&config);
+    // Load AWS SDK configuration (credentials, region, endpoint).
+    // This leverages standard AWS environment variables or config files.
+    let config = aws_config::load_from_env().await;
+    let s3_client = aws_sdk_s3::Client::new(&config);
 
-        log::info!("Starting CSV processing: Input='{}', Output='{}'", s3_input_path, s3_output_path);
-        log::debug!("Processing parameters: {:?}", params);
+    log::info!("Starting CSV processing: Input='{}', Output='{}'", s3_input_path, s3_output_path);
+    log::debug!("Processing parameters: {:?}", params);
 
-        // Step 1: Read the input file as a byte stream from S3.
-        let input_byte_stream = file_handler::read_stream_from_s3(&s3_client, &s3_input_path)
-            .await
-            .context(format!("Failed to read data stream from S3 path: '{}'", s3_input_path))
-            .map_err(KaryakshamError::IoError)?;
-
-        // Step 2: Process the data stream. This involves parsing the CSV, applying
-        // filters and transformations, and potentially converting to a new format.
-        let processed_byte_stream = data_processor::process_csv_data(
-            input_byte_stream,
-            params,
-        )
+    // Step 1: Read the input file as a byte stream from S3.
+    let input_byte_stream = file_handler::read_stream_from_s3(&s3_client, &s3_input_path)
         .await
-        .context("Failed during high-performance data processing.")
-        .map_err(KaryakshamError::ProcessingError)?;
+        .context(format!("Failed to read data stream from S3 path: '{}'", s3_input_path))
+        .map_err(KaryakshamError::IoError)?;
 
-        // Step 3: Write the processed byte stream back to S3.
-        file_handler::write_stream_to_s3(&s3_client, &s3_output_path, processed_byte_stream)
-            .await
-            .context(format!("Failed to write processed data to S3 path: '{}'", s3_output_path))
-            .map_err(KaryakshamError::IoError)?;
+    // Step 2: Process the data stream. This involves parsing the CSV, applying
+    // filters and transformations, and potentially converting to a new format.
+    let processed_byte_stream = data_processor::process_csv_data(
+        input_byte_stream,
+        params,
+        progress_callback,
+    )
+    .await
+    .context("Failed during high-performance data processing.")
+    .map_err(KaryakshamError::ProcessingError)?;
 
-        log::info!("Successfully processed CSV: Input='{}', Output='{}'", s3_input_path, s3_output_path);
+    // Step 3: Write the processed byte stream back to S3.
+    file_handler::write_stream_to_s3(&s3_client, &s3_output_path, processed_byte_stream)
+        .await
+        .context(format!("Failed to write processed data to S3 path: '{}'", s3_output_path))
+        .map_err(KaryakshamError::IoError)?;
 
-        Ok(s3_output_path) // Return the output path as confirmation
-    })
-    .map_err(PyErr::from) // Convert the error from the async block into a PyErr
+    log::info!("Successfully processed CSV: Input='{}', Output='{}'", s3_input_path, s3_output_path);
+
+    Ok(s3_output_path) // Return the output path as confirmation
 }
 
 /// A simple test function to verify PyO3 bindings and basic Rust execution.
@@ -195,10 +333,16 @@ fn karyaksham_rust_engine(_py: Python, m: &PyModule) -> PyResult<()> {
     // Add the custom exception type to the Python module so it can be caught
     // by Python code.
     m.add("KaryakshamRustEngineError", _py.get_type::<KaryakshamRustEngineError>())?;
+    m.add("KaryakshamIoError", _py.get_type::<KaryakshamIoError>())?;
+    m.add("KaryakshamParameterError", _py.get_type::<KaryakshamParameterError>())?;
+    m.add("KaryakshamProcessingError", _py.get_type::<KaryakshamProcessingError>())?;
+    m.add("KaryakshamRuntimeError", _py.get_type::<KaryakshamRuntimeError>())?;
 
     // Add the exposed Rust functions to the Python module.
     // `wrap_pyfunction!` macro handles the necessary boilerplate for binding.
     m.add_function(wrap_pyfunction!(process_csv_file, m)?)?;
+    m.add_function(wrap_pyfunction!(process_csv_file_async, m)?)?;
+    m.add_function(wrap_pyfunction!(configure_runtime, m)?)?;
     m.add_function(wrap_pyfunction!(rust_hello_world, m)?)?;
 
     Ok(())
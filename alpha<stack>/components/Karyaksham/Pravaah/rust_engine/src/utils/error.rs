@@ -1,76 +1,34 @@
-```rust
-use pyo3::{
-    exceptions::{PyIOError, PyOSError, PyRuntimeError, PyValueError},
-    PyErr,
-};
 use thiserror::Error;
 
-/// Custom error type for the Karyaksham Rust processing engine.
+/// Custom error type for the Karyaksham Rust engine's Python-facing entry points
+/// (`process_csv_file` and friends in `lib.rs`). Each variant wraps the `anyhow::Error` chain
+/// built up by the `.context(...)` calls at each pipeline stage, so the original cause is never
+/// discarded — only classified, for `impl From<KaryakshamError> for PyErr` in `lib.rs` to raise
+/// the matching Python exception subclass.
 ///
-/// This enum centralizes various failure conditions that can occur within the
-/// Rust engine, providing a unified error handling mechanism. It integrates
-/// seamlessly with PyO3 for error propagation to Python.
+/// Note: `file_handler.rs` and `data_processor.rs` construct some of these same variant names
+/// (`IoError`, `ConfigurationError`, `InputError`, `InvalidRange`, `NotImplemented`, ...) directly
+/// from plain `String`s rather than `anyhow::Error`s. That predates this definition and is out of
+/// scope here; those call sites remain unreconciled with this enum.
 #[derive(Error, Debug)]
-pub enum EngineError {
-    /// Represents an underlying I/O error (e.g., file system, network streams).
+pub enum KaryakshamError {
+    /// An I/O failure — reading from or writing to S3, or a local filesystem error.
     #[error("I/O error: {0}")]
-    Io(#[from] std::io::Error),
+    IoError(anyhow::Error),
 
-    /// Represents an error specifically from object storage operations (e.g., S3, MinIO).
-    /// The string provides a descriptive message about the storage error.
-    #[error("Object storage error: {0}")]
-    ObjectStorage(String),
+    /// The caller-supplied processing parameters (e.g. the `processing_params_json` string)
+    /// were invalid or failed to deserialize.
+    #[error("Invalid processing parameters: {0}")]
+    ParameterError(anyhow::Error),
 
-    /// Represents an error during core data processing or transformation logic.
-    #[error("Data processing error: {0}")]
-    DataProcessing(String),
+    /// The core CSV/data processing pipeline itself failed (parsing, filtering, transforming).
+    #[error("Data processing failed: {0}")]
+    ProcessingError(anyhow::Error),
 
-    /// Wraps errors originating from the `polars` DataFrame library.
-    #[error("Polars error: {0}")]
-    Polars(#[from] polars::error::PolarsError),
-
-    /// Represents an error during serialization or deserialization operations (e.g., JSON, BSON).
-    #[error("Serialization error: {0}")]
-    Serialization(#[from] serde_json::Error),
-
-    /// Represents an error due to invalid input data or parameters.
-    #[error("Validation error: {0}")]
-    Validation(String),
-
-    /// Represents an unexpected internal state or logic error within the engine.
-    /// This should ideally be caught during development, but serves as a fallback.
-    #[error("Internal engine error: {0}")]
-    Internal(String),
+    /// The Tokio runtime or other execution infrastructure failed to initialize or run.
+    #[error("Runtime error: {0}")]
+    RuntimeError(anyhow::Error),
 }
 
-/// Implements conversion from `EngineError` to `pyo3::PyErr`.
-///
-/// This allows Rust `Result<T, EngineError>` to be directly returned from
-/// `#[pyfunction]` or `#[pymethods]` functions, which PyO3 then automatically
-/// converts into a Python exception, ensuring proper error propagation from
-/// Rust to the Python interpreter. Each `EngineError` variant is mapped to
-/// an appropriate Python exception type for clarity and conventional error handling.
-impl From<EngineError> for PyErr {
-    fn from(err: EngineError) -> PyErr {
-        match err {
-            EngineError::Io(e) => PyIOError::new_err(format!("Karyaksham I/O Error: {}", e)),
-            EngineError::ObjectStorage(e) => {
-                PyOSError::new_err(format!("Karyaksham Object Storage Error: {}", e))
-            }
-            EngineError::DataProcessing(e) => {
-                PyValueError::new_err(format!("Karyaksham Data Processing Error: {}", e))
-            }
-            EngineError::Polars(e) => PyValueError::new_err(format!("Karyaksham Polars Error: {}", e)),
-            EngineError::Serialization(e) => {
-                PyValueError::new_err(format!("Karyaksham Serialization Error: {}", e))
-            }
-            EngineError::Validation(e) => {
-                PyValueError::new_err(format!("Karyaksham Validation Error: {}", e))
-            }
-            EngineError::Internal(e) => {
-                PyRuntimeError::new_err(format!("Karyaksham Internal Engine Error: {}", e))
-            }
-        }
-    }
-}
-```
\ No newline at end of file
+/// A specialized `Result` type for the Karyaksham engine's Python-facing entry points.
+pub type Result<T> = std::result::Result<T, KaryakshamError>;
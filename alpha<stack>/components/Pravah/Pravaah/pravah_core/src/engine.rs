@@ -8,7 +8,13 @@ use walkdir::WalkDir;
 use futures::stream::{FuturesOrdered, StreamExt};
 use rayon::prelude::*; // Used for potential CPU-bound operations within file processing
 
-use crate::models::{JobParameters, ProcessingResult, ProcessingAction};
+use async_compression::tokio::bufread::{BzDecoder, GzipDecoder, ZstdDecoder};
+use async_compression::tokio::write::{BzEncoder, GzipEncoder, ZstdEncoder};
+use async_compression::Level;
+
+use std::collections::HashMap;
+
+use crate::models::{Codec, ImageFormat, JobParameters, JobStatus, ProcessingAction, ProcessingResult};
 use crate::error::PravahError;
 
 /// This trait defines the interface for different file processing strategies.
@@ -124,6 +130,581 @@ impl FileProcessor for ExtractFirstLinesProcessor {
     }
 }
 
+/// A processor that streams a file through a compression codec, never buffering the
+/// whole file in memory. The output path is expected to already carry the codec's
+/// extension (`process_data` appends it when selecting this action).
+struct CompressFileProcessor {
+    codec: Codec,
+    level: u32,
+}
+
+#[async_trait::async_trait]
+impl FileProcessor for CompressFileProcessor {
+    async fn process_file(&self, input_path: &Path, output_path: &Path) -> Result<(), PravahError> {
+        log::debug!("Processing file (compress, {:?}): {:?}", self.codec, input_path);
+
+        let mut input_file = File::open(input_path).await.map_err(|e| {
+            PravahError::IoError(io::Error::new(e.kind(), format!("Failed to open input file {:?}: {}", input_path, e)))
+        })?;
+
+        let parent_dir = output_path.parent().ok_or_else(|| {
+            PravahError::PathError(format!("Output path {:?} has no parent directory", output_path))
+        })?;
+        tokio::fs::create_dir_all(parent_dir).await.map_err(|e| {
+            PravahError::IoError(io::Error::new(e.kind(), format!("Failed to create output directory {:?}: {}", parent_dir, e)))
+        })?;
+
+        let output_file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(output_path)
+            .await
+            .map_err(|e| {
+                PravahError::IoError(io::Error::new(e.kind(), format!("Failed to open output file {:?}: {}", output_path, e)))
+            })?;
+
+        let level = Level::Precise(self.level as i32);
+
+        // Wrap the output in the chosen encoder and stream through it so the whole file is
+        // never buffered in memory; the encoder is flushed and shut down at the end so any
+        // trailing frame/footer bytes get written out.
+        match self.codec {
+            Codec::Gzip => {
+                let mut encoder = GzipEncoder::with_quality(output_file, level);
+                io::copy(&mut input_file, &mut encoder).await.map_err(|e| {
+                    PravahError::IoError(io::Error::new(e.kind(), format!("Failed to compress {:?}: {}", input_path, e)))
+                })?;
+                encoder.shutdown().await.map_err(|e| {
+                    PravahError::IoError(io::Error::new(e.kind(), format!("Failed to finalize gzip stream for {:?}: {}", input_path, e)))
+                })?;
+            }
+            Codec::Bzip2 => {
+                let mut encoder = BzEncoder::with_quality(output_file, level);
+                io::copy(&mut input_file, &mut encoder).await.map_err(|e| {
+                    PravahError::IoError(io::Error::new(e.kind(), format!("Failed to compress {:?}: {}", input_path, e)))
+                })?;
+                encoder.shutdown().await.map_err(|e| {
+                    PravahError::IoError(io::Error::new(e.kind(), format!("Failed to finalize bzip2 stream for {:?}: {}", input_path, e)))
+                })?;
+            }
+            Codec::Zstd => {
+                let mut encoder = ZstdEncoder::with_quality(output_file, level);
+                io::copy(&mut input_file, &mut encoder).await.map_err(|e| {
+                    PravahError::IoError(io::Error::new(e.kind(), format!("Failed to compress {:?}: {}", input_path, e)))
+                })?;
+                encoder.shutdown().await.map_err(|e| {
+                    PravahError::IoError(io::Error::new(e.kind(), format!("Failed to finalize zstd stream for {:?}: {}", input_path, e)))
+                })?;
+            }
+        }
+
+        log::info!("Successfully compressed {:?} to {:?} with {:?}", input_path, output_path, self.codec);
+        Ok(())
+    }
+}
+
+/// A processor that streams a file through a decompression codec. When `codec_override` is
+/// `None`, the codec is inferred from the input file's extension (`.gz`/`.bz2`/`.zst`).
+struct DecompressFileProcessor {
+    codec_override: Option<Codec>,
+}
+
+#[async_trait::async_trait]
+impl FileProcessor for DecompressFileProcessor {
+    async fn process_file(&self, input_path: &Path, output_path: &Path) -> Result<(), PravahError> {
+        let codec = self.codec_override.or_else(|| {
+            input_path
+                .extension()
+                .and_then(|s| s.to_str())
+                .and_then(Codec::from_extension)
+        }).ok_or_else(|| {
+            PravahError::Unsupported(format!(
+                "Could not infer decompression codec for {:?}; specify one explicitly",
+                input_path
+            ))
+        })?;
+
+        log::debug!("Processing file (decompress, {:?}): {:?}", codec, input_path);
+
+        let input_file = File::open(input_path).await.map_err(|e| {
+            PravahError::IoError(io::Error::new(e.kind(), format!("Failed to open input file {:?}: {}", input_path, e)))
+        })?;
+        let buffered_input = tokio::io::BufReader::new(input_file);
+
+        let parent_dir = output_path.parent().ok_or_else(|| {
+            PravahError::PathError(format!("Output path {:?} has no parent directory", output_path))
+        })?;
+        tokio::fs::create_dir_all(parent_dir).await.map_err(|e| {
+            PravahError::IoError(io::Error::new(e.kind(), format!("Failed to create output directory {:?}: {}", parent_dir, e)))
+        })?;
+
+        let mut output_file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(output_path)
+            .await
+            .map_err(|e| {
+                PravahError::IoError(io::Error::new(e.kind(), format!("Failed to open output file {:?}: {}", output_path, e)))
+            })?;
+
+        match codec {
+            Codec::Gzip => {
+                let mut decoder = GzipDecoder::new(buffered_input);
+                io::copy(&mut decoder, &mut output_file).await
+            }
+            Codec::Bzip2 => {
+                let mut decoder = BzDecoder::new(buffered_input);
+                io::copy(&mut decoder, &mut output_file).await
+            }
+            Codec::Zstd => {
+                let mut decoder = ZstdDecoder::new(buffered_input);
+                io::copy(&mut decoder, &mut output_file).await
+            }
+        }
+        .map_err(|e| {
+            PravahError::IoError(io::Error::new(e.kind(), format!("Failed to decompress {:?}: {}", input_path, e)))
+        })?;
+        output_file.flush().await.map_err(|e| {
+            PravahError::IoError(io::Error::new(e.kind(), format!("Failed to flush output file {:?}: {}", output_path, e)))
+        })?;
+
+        log::info!("Successfully decompressed {:?} to {:?} with {:?}", input_path, output_path, codec);
+        Ok(())
+    }
+}
+
+/// A processor that pipes a file through an external command: the file's bytes are streamed
+/// to the child's stdin and its stdout is streamed to the output path. Stderr is drained
+/// concurrently on its own task so a chatty child can't deadlock the transform by filling its
+/// stderr pipe buffer while we're still waiting on stdout/stdin.
+struct ShellFileProcessor {
+    command: String,
+    args: Vec<String>,
+}
+
+#[async_trait::async_trait]
+impl FileProcessor for ShellFileProcessor {
+    async fn process_file(&self, input_path: &Path, output_path: &Path) -> Result<(), PravahError> {
+        use std::process::Stdio;
+
+        log::debug!("Processing file (shell: {} {:?}): {:?}", self.command, self.args, input_path);
+
+        let parent_dir = output_path.parent().ok_or_else(|| {
+            PravahError::PathError(format!("Output path {:?} has no parent directory", output_path))
+        })?;
+        tokio::fs::create_dir_all(parent_dir).await.map_err(|e| {
+            PravahError::IoError(io::Error::new(e.kind(), format!("Failed to create output directory {:?}: {}", parent_dir, e)))
+        })?;
+
+        let mut child = tokio::process::Command::new(&self.command)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                PravahError::IoError(io::Error::new(e.kind(), format!("Failed to spawn '{}' for {:?}: {}", self.command, input_path, e)))
+            })?;
+
+        let mut child_stdin = child.stdin.take().expect("child stdin was piped");
+        let child_stdout = child.stdout.take().expect("child stdout was piped");
+        let child_stderr = child.stderr.take().expect("child stderr was piped");
+
+        // Drain stderr on its own task so a large amount of diagnostic output can't fill the
+        // pipe buffer and stall the child while we're still feeding stdin / reading stdout.
+        let stderr_task = tokio::spawn(async move {
+            let mut stderr_buf = Vec::new();
+            let mut reader = child_stderr;
+            let _ = reader.read_to_end(&mut stderr_buf).await;
+            stderr_buf
+        });
+
+        let input_path_owned = input_path.to_path_buf();
+        let stdin_task = tokio::spawn(async move {
+            let mut input_file = File::open(&input_path_owned).await?;
+            io::copy(&mut input_file, &mut child_stdin).await?;
+            // Dropping `child_stdin` here (end of scope) closes the pipe, signalling EOF to
+            // the child; shutdown() makes that explicit before the drop.
+            child_stdin.shutdown().await?;
+            Ok::<(), io::Error>(())
+        });
+
+        let mut output_file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(output_path)
+            .await
+            .map_err(|e| {
+                PravahError::IoError(io::Error::new(e.kind(), format!("Failed to open output file {:?}: {}", output_path, e)))
+            })?;
+
+        let mut child_stdout = child_stdout;
+        io::copy(&mut child_stdout, &mut output_file).await.map_err(|e| {
+            PravahError::IoError(io::Error::new(e.kind(), format!("Failed to read command output for {:?}: {}", input_path, e)))
+        })?;
+        output_file.flush().await.map_err(|e| {
+            PravahError::IoError(io::Error::new(e.kind(), format!("Failed to flush output file {:?}: {}", output_path, e)))
+        })?;
+
+        if let Err(e) = stdin_task.await.expect("stdin feeder task panicked") {
+            log::warn!("Error writing to child stdin for {:?}: {}", input_path, e);
+        }
+        let stderr_bytes = stderr_task.await.unwrap_or_default();
+
+        let status = child.wait().await.map_err(|e| {
+            PravahError::IoError(io::Error::new(e.kind(), format!("Failed to wait on '{}' for {:?}: {}", self.command, input_path, e)))
+        })?;
+
+        if !status.success() {
+            return Err(PravahError::ProcessError {
+                path: input_path.to_path_buf(),
+                code: status.code(),
+                stderr: String::from_utf8_lossy(&stderr_bytes).into_owned(),
+            });
+        }
+
+        log::info!("Successfully piped {:?} through '{}' to {:?}", input_path, self.command, output_path);
+        Ok(())
+    }
+}
+
+/// Builds the `FileProcessor` for a given `ProcessingAction`, recursing into `Pipeline`'s
+/// nested steps. Pulled out of `process_data` so `PipelineProcessor` can reuse it without
+/// duplicating the dispatch match.
+fn build_processor(action: &ProcessingAction) -> Result<Arc<dyn FileProcessor + Send + Sync>, PravahError> {
+    Ok(match action {
+        ProcessingAction::Copy => Arc::new(CopyFileProcessor),
+        ProcessingAction::ExtractFirstLines { num_lines } => {
+            Arc::new(ExtractFirstLinesProcessor { num_lines: *num_lines })
+        }
+        ProcessingAction::Compress { codec, level } => {
+            Arc::new(CompressFileProcessor { codec: *codec, level: *level })
+        }
+        ProcessingAction::Decompress { codec } => Arc::new(DecompressFileProcessor { codec_override: *codec }),
+        ProcessingAction::Shell { command, args } => {
+            Arc::new(ShellFileProcessor { command: command.clone(), args: args.clone() })
+        }
+        ProcessingAction::ExtractMediaMetadata => Arc::new(ExtractMediaMetadataProcessor),
+        ProcessingAction::ConvertImage { target, max_dimension } => {
+            Arc::new(ConvertImageProcessor { target: *target, max_dimension: *max_dimension })
+        }
+        ProcessingAction::Pipeline { steps } => {
+            let steps = steps.iter().map(build_processor).collect::<Result<Vec<_>, _>>()?;
+            Arc::new(PipelineProcessor { steps })
+        }
+    })
+}
+
+/// Runs `extract_media_metadata` and writes the resulting metadata (plus any warnings) to
+/// `output_path` as JSON, so `ProcessingType::ExtractMediaMetadata`/`ProcessingAction::ExtractMediaMetadata`
+/// produces a real, inspectable artifact per file.
+struct ExtractMediaMetadataProcessor;
+
+#[async_trait::async_trait]
+impl FileProcessor for ExtractMediaMetadataProcessor {
+    async fn process_file(&self, input_path: &Path, output_path: &Path) -> Result<(), PravahError> {
+        let (custom_metrics, warnings) = extract_media_metadata(input_path).await?;
+        for warning in &warnings {
+            log::warn!("{}", warning);
+        }
+
+        let payload = serde_json::json!({ "custom_metrics": custom_metrics, "warnings": warnings });
+        let bytes = serde_json::to_vec_pretty(&payload).map_err(|e| PravahError::Processing {
+            path: Some(input_path.to_path_buf()),
+            message: format!("Failed to serialize media metadata: {}", e),
+        })?;
+
+        if let Some(parent) = output_path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(PravahError::Io)?;
+        }
+        tokio::fs::write(output_path, bytes).await.map_err(PravahError::Io)?;
+
+        Ok(())
+    }
+}
+
+/// Maps our `ImageFormat` to the `image` crate's own format enum. `Heif` and `Svg` have no
+/// encode support (`Heif` needs a feature this build doesn't enable; rasterizing *to* `Svg`
+/// isn't meaningful), so both are rejected here rather than attempted.
+fn to_encodable_image_format(format: ImageFormat) -> Result<image::ImageFormat, PravahError> {
+    match format {
+        ImageFormat::Jpeg => Ok(image::ImageFormat::Jpeg),
+        ImageFormat::Png => Ok(image::ImageFormat::Png),
+        ImageFormat::Webp => Ok(image::ImageFormat::WebP),
+        ImageFormat::Avif => Ok(image::ImageFormat::Avif),
+        ImageFormat::Gif => Ok(image::ImageFormat::Gif),
+        ImageFormat::Tiff => Ok(image::ImageFormat::Tiff),
+        ImageFormat::Bmp => Ok(image::ImageFormat::Bmp),
+        ImageFormat::Heif => Err(PravahError::Validation(
+            "ConvertImage target HEIF requires the image crate's heif-encoder feature, which isn't enabled in this build".to_string(),
+        )),
+        ImageFormat::Svg => Err(PravahError::Validation(
+            "ConvertImage target must not be SVG; rasterizing to a vector format isn't supported".to_string(),
+        )),
+    }
+}
+
+/// Converts an image to `target`, preserving aspect ratio. A raster source is downscaled (never
+/// upscaled) to fit within `max_dimension` on its longest side. An `Svg` source is rasterized
+/// instead, at a scale factor of `target longest side / source longest side`, so its longest
+/// side lands on `max_dimension` whether that is larger or smaller than the SVG's own size.
+///
+/// Needs `image` (raster decode/resize/encode) and `resvg`/`usvg`/`tiny_skia` (SVG
+/// rasterization) added to Cargo.toml.
+struct ConvertImageProcessor {
+    target: ImageFormat,
+    max_dimension: Option<u32>,
+}
+
+impl ConvertImageProcessor {
+    fn convert(input_path: &Path, output_path: &Path, target: ImageFormat, max_dimension: Option<u32>) -> Result<(), PravahError> {
+        let is_svg = input_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("svg"))
+            .unwrap_or(false);
+
+        let mut image = if is_svg {
+            Self::rasterize_svg(input_path, max_dimension)?
+        } else {
+            image::io::Reader::open(input_path)
+                .map_err(|e| PravahError::Processing {
+                    path: Some(input_path.to_path_buf()),
+                    message: format!("Failed to open image: {}", e),
+                })?
+                .with_guessed_format()
+                .map_err(|e| PravahError::Processing {
+                    path: Some(input_path.to_path_buf()),
+                    message: format!("Failed to detect image format: {}", e),
+                })?
+                .decode()
+                .map_err(|e| PravahError::Processing {
+                    path: Some(input_path.to_path_buf()),
+                    message: format!("Failed to decode image: {}", e),
+                })?
+        };
+
+        // SVG sources are already rasterized to `max_dimension` by `rasterize_svg`, so only
+        // raster sources need a separate downscale pass here.
+        if !is_svg {
+            if let Some(max_dimension) = max_dimension {
+                let longest_side = image.width().max(image.height());
+                if longest_side > max_dimension {
+                    let scale = max_dimension as f64 / longest_side as f64;
+                    let new_width = ((image.width() as f64 * scale).round() as u32).max(1);
+                    let new_height = ((image.height() as f64 * scale).round() as u32).max(1);
+                    image = image.resize(new_width, new_height, image::imageops::FilterType::Lanczos3);
+                }
+            }
+        }
+
+        let image_format = to_encodable_image_format(target)?;
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent).map_err(PravahError::Io)?;
+        }
+        image.save_with_format(output_path, image_format).map_err(|e| PravahError::Processing {
+            path: Some(output_path.to_path_buf()),
+            message: format!("Failed to encode image as {:?}: {}", target, e),
+        })
+    }
+
+    fn rasterize_svg(input_path: &Path, max_dimension: Option<u32>) -> Result<image::DynamicImage, PravahError> {
+        let svg_data = std::fs::read(input_path).map_err(PravahError::Io)?;
+        let options = usvg::Options::default();
+        let tree = usvg::Tree::from_data(&svg_data, &options.to_ref()).map_err(|e| PravahError::Processing {
+            path: Some(input_path.to_path_buf()),
+            message: format!("Failed to parse SVG: {}", e),
+        })?;
+
+        let source_size = tree.svg_node().size;
+        let source_longest = source_size.width().max(source_size.height()).max(1.0);
+        // The scale factor is target longest side / source longest side, so rasterization
+        // lands directly on `max_dimension` instead of rasterizing at native size and
+        // downscaling again.
+        let scale = max_dimension.map(|max_dimension| max_dimension as f64 / source_longest).unwrap_or(1.0);
+        let pixmap_width = ((source_size.width() * scale).round() as u32).max(1);
+        let pixmap_height = ((source_size.height() * scale).round() as u32).max(1);
+
+        let mut pixmap = tiny_skia::Pixmap::new(pixmap_width, pixmap_height).ok_or_else(|| PravahError::Processing {
+            path: Some(input_path.to_path_buf()),
+            message: "Failed to allocate a rasterization surface for this SVG".to_string(),
+        })?;
+        resvg::render(
+            &tree,
+            usvg::FitTo::Size(pixmap_width, pixmap_height),
+            tiny_skia::Transform::identity(),
+            pixmap.as_mut(),
+        );
+
+        image::RgbaImage::from_raw(pixmap_width, pixmap_height, pixmap.data().to_vec())
+            .map(image::DynamicImage::ImageRgba8)
+            .ok_or_else(|| PravahError::Processing {
+                path: Some(input_path.to_path_buf()),
+                message: "Failed to build an image buffer from the rasterized SVG".to_string(),
+            })
+    }
+}
+
+#[async_trait::async_trait]
+impl FileProcessor for ConvertImageProcessor {
+    async fn process_file(&self, input_path: &Path, output_path: &Path) -> Result<(), PravahError> {
+        let input_path = input_path.to_path_buf();
+        let output_path = output_path.to_path_buf();
+        let target = self.target;
+        let max_dimension = self.max_dimension;
+
+        // Decoding/resizing/encoding via the `image`/`resvg` crates is synchronous and
+        // CPU-bound, so it runs on the blocking thread pool instead of an async worker thread.
+        tokio::task::spawn_blocking(move || Self::convert(&input_path, &output_path, target, max_dimension))
+            .await
+            .map_err(|e| PravahError::Internal(format!("Image conversion task panicked: {}", e)))?
+    }
+}
+
+/// Chains `steps`, feeding each step's output forward as the next step's input and writing
+/// only the final step's output to `output_path`. Intermediate outputs are written to temp
+/// files alongside `output_path` and removed once the pipeline finishes (or fails).
+struct PipelineProcessor {
+    steps: Vec<Arc<dyn FileProcessor + Send + Sync>>,
+}
+
+#[async_trait::async_trait]
+impl FileProcessor for PipelineProcessor {
+    async fn process_file(&self, input_path: &Path, output_path: &Path) -> Result<(), PravahError> {
+        if self.steps.is_empty() {
+            return Err(PravahError::Validation("Pipeline has no steps".to_string()));
+        }
+
+        let last_index = self.steps.len() - 1;
+        let mut current_input = input_path.to_path_buf();
+        let mut temp_outputs = Vec::new();
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let step_output = if index == last_index {
+                output_path.to_path_buf()
+            } else {
+                let file_name = output_path.file_name().and_then(|n| n.to_str()).unwrap_or("output");
+                output_path.with_file_name(format!("{}.pipeline_step_{}.tmp", file_name, index))
+            };
+            if let Some(parent) = step_output.parent() {
+                tokio::fs::create_dir_all(parent).await.map_err(PravahError::Io)?;
+            }
+
+            if index != last_index {
+                temp_outputs.push(step_output.clone());
+            }
+
+            let started_at = std::time::Instant::now();
+            let step_result = step.process_file(&current_input, &step_output).await;
+            log::debug!("Pipeline step {} finished in {:?}", index, started_at.elapsed());
+
+            if let Err(e) = step_result {
+                for temp_output in &temp_outputs {
+                    let _ = tokio::fs::remove_file(temp_output).await;
+                }
+                return Err(PravahError::Processing {
+                    path: Some(input_path.to_path_buf()),
+                    message: format!("pipeline step {} failed: {}", index, e),
+                });
+            }
+
+            current_input = step_output;
+        }
+
+        for temp_output in &temp_outputs {
+            let _ = tokio::fs::remove_file(temp_output).await;
+        }
+        Ok(())
+    }
+}
+
+/// Runs `ffprobe` against `path` and extracts a handful of commonly-wanted media metadata
+/// fields, for `ProcessingAction::ExtractMediaMetadata`.
+///
+/// Unlike [`ShellFileProcessor`], this isn't a byte-for-byte pipe: `ffprobe` is invoked once
+/// with `-print_format json` and its stdout is parsed as a single JSON document, so there's no
+/// stdin to feed and no output file to stream into — only stderr needs draining.
+///
+/// A file ffprobe can open but that yields an empty or missing `streams` array (e.g. a
+/// non-media file, or a container with no decodable streams) is not treated as a failure: it
+/// comes back as `Ok` with whatever format-level fields were found (possibly none) and a
+/// warning describing the situation, which the caller logs and writes into the per-file JSON
+/// output alongside the metadata. Only a genuine invocation or parse
+/// failure (ffprobe missing, non-zero exit, unparseable stdout) returns `Err(PravahError::Ffprobe)`.
+pub async fn extract_media_metadata(path: &Path) -> Result<(HashMap<String, String>, Vec<String>), PravahError> {
+    log::debug!("Extracting media metadata (ffprobe): {:?}", path);
+
+    let output = tokio::process::Command::new("ffprobe")
+        .args(["-v", "quiet", "-print_format", "json", "-show_format", "-show_streams"])
+        .arg(path)
+        .output()
+        .await
+        .map_err(|e| PravahError::Ffprobe {
+            path: path.to_path_buf(),
+            message: format!("Failed to spawn ffprobe: {}", e),
+        })?;
+
+    if !output.status.success() {
+        return Err(PravahError::Ffprobe {
+            path: path.to_path_buf(),
+            message: format!(
+                "ffprobe exited with {:?}: {}",
+                output.status.code(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).map_err(|e| PravahError::Ffprobe {
+        path: path.to_path_buf(),
+        message: format!("Failed to parse ffprobe output as JSON: {}", e),
+    })?;
+
+    let mut custom_metrics = HashMap::new();
+    if let Some(format) = parsed.get("format") {
+        if let Some(duration) = format.get("duration").and_then(|v| v.as_str()) {
+            custom_metrics.insert("duration_seconds".to_string(), duration.to_string());
+        }
+        if let Some(bit_rate) = format.get("bit_rate").and_then(|v| v.as_str()) {
+            custom_metrics.insert("bitrate".to_string(), bit_rate.to_string());
+        }
+    }
+
+    let streams = parsed.get("streams").and_then(|v| v.as_array()).filter(|s| !s.is_empty());
+    let Some(streams) = streams else {
+        return Ok((
+            custom_metrics,
+            vec![format!("ffprobe reported no streams for {:?}; metadata is incomplete", path)],
+        ));
+    };
+
+    // Prefer the first video stream for codec/width/height/frame_rate, since that's what
+    // callers most often care about; fall back to the first stream of any kind (e.g. a
+    // standalone audio file).
+    let primary_stream = streams
+        .iter()
+        .find(|s| s.get("codec_type").and_then(|v| v.as_str()) == Some("video"))
+        .unwrap_or(&streams[0]);
+
+    if let Some(codec) = primary_stream.get("codec_name").and_then(|v| v.as_str()) {
+        custom_metrics.insert("codec".to_string(), codec.to_string());
+    }
+    if let Some(width) = primary_stream.get("width").and_then(|v| v.as_u64()) {
+        custom_metrics.insert("width".to_string(), width.to_string());
+    }
+    if let Some(height) = primary_stream.get("height").and_then(|v| v.as_u64()) {
+        custom_metrics.insert("height".to_string(), height.to_string());
+    }
+    if let Some(frame_rate) = primary_stream.get("avg_frame_rate").and_then(|v| v.as_str()) {
+        custom_metrics.insert("frame_rate".to_string(), frame_rate.to_string());
+    }
+
+    Ok((custom_metrics, Vec::new()))
+}
+
 /// The main asynchronous function to process data based on job parameters.
 /// This function coordinates directory traversal, file filtering, and parallel processing.
 pub async fn process_data(params: JobParameters) -> ProcessingResult {
@@ -137,11 +718,45 @@ pub async fn process_data(params: JobParameters) -> ProcessingResult {
     let total_files_processed = Arc::new(AtomicUsize::new(0));
     let errors_encountered = Arc::new(AtomicUsize::new(0));
 
+    // Fall back to a terminal progress bar when no reporter is set (the case when `JobParameters`
+    // arrives from Python without a `progress_callback`, since the reporter field isn't part of
+    // the serialized job payload).
+    let reporter: Arc<dyn crate::progress::ProgressReporter> = params
+        .progress_reporter
+        .clone()
+        .unwrap_or_else(|| Arc::new(crate::progress::TerminalProgressReporter::new()));
+
+    // Cooperative cancellation: if the caller didn't supply a token (e.g. embedding
+    // `pravah_core` without wiring one up), fall back to one that never fires, so the
+    // cancellation checks below are unconditional rather than branching on `Option`.
+    let cancellation_token = params
+        .cancellation_token
+        .clone()
+        .unwrap_or_else(|| Arc::new(tokio_util::sync::CancellationToken::new()));
+    let mut skipped_files: u64 = 0;
+
     // Select the appropriate file processor based on the requested action
-    let processor: Arc<dyn FileProcessor + Send + Sync> = match params.processing_action {
-        ProcessingAction::Copy => Arc::new(CopyFileProcessor),
-        ProcessingAction::ExtractFirstLines { num_lines } => Arc::new(ExtractFirstLinesProcessor { num_lines }),
-        // Extend with more processing actions here
+    let processor: Arc<dyn FileProcessor + Send + Sync> = match build_processor(&params.processing_action) {
+        Ok(processor) => processor,
+        Err(e) => {
+            log::error!("Failed to build a processor for job {}: {}", params.job_id, e);
+            return ProcessingResult {
+                job_id: params.job_id.clone(),
+                file_path: params.input_path.clone(),
+                status: JobStatus::Failed,
+                tenant: String::new(),
+                message: Some(e.to_string()),
+                output_path: Some(params.output_path.clone()),
+                processing_time_ms: 0,
+                bytes_processed: 0,
+                custom_metrics: None,
+                total_files_processed: 0,
+                errors_encountered: 0,
+                cancelled: false,
+                skipped_files: 0,
+                warnings: Vec::new(),
+            };
+        }
     };
 
     // Semaphore to limit the number of concurrently executing file processing tasks
@@ -199,9 +814,17 @@ pub async fn process_data(params: JobParameters) -> ProcessingResult {
         // Calculate the output path, preserving the relative directory structure from the input_path
         let relative_path = entry_path.strip_prefix(&input_path)
             .map_err(|e| PravahError::PathError(format!("Failed to strip prefix from {:?}: {}", entry_path, e)));
-        
+
         let output_file_path = match relative_path {
-            Ok(rel_path) => output_base_path.join(rel_path),
+            Ok(rel_path) => {
+                // A prior run (tracked in a `JobCheckpoint` and replayed via `JobInput.resume_from`)
+                // already completed this file; skip it rather than reprocessing from scratch.
+                if params.skip_relative_paths.contains(&rel_path.to_string_lossy().into_owned()) {
+                    log::debug!("Skipping already-completed file {:?} (resumed from checkpoint)", entry_path);
+                    continue;
+                }
+                output_base_path.join(rel_path)
+            }
             Err(e) => {
                 log::error!("Error determining relative path for {:?}: {}", entry_path, e);
                 errors_encountered.fetch_add(1, Ordering::Relaxed);
@@ -209,16 +832,52 @@ pub async fn process_data(params: JobParameters) -> ProcessingResult {
             }
         };
 
+        // Compress/Decompress actions change the file name on the output side: compressing
+        // appends the codec's suffix, decompressing strips a recognized one.
+        let output_file_path = match &params.processing_action {
+            ProcessingAction::Compress { codec, .. } => {
+                let mut path_str = output_file_path.into_os_string();
+                path_str.push(".");
+                path_str.push(codec.extension());
+                PathBuf::from(path_str)
+            }
+            ProcessingAction::Decompress { .. } => {
+                match entry_path.extension().and_then(|s| s.to_str()).and_then(Codec::from_extension) {
+                    Some(_) => output_file_path.with_extension(""),
+                    None => output_file_path,
+                }
+            }
+            _ => output_file_path,
+        };
+
+        reporter.on_discovered(&entry_path);
+
+        // Once cancellation fires, stop spawning new tasks but keep traversing so we can
+        // report an accurate `skipped_files` count; in-flight tasks already spawned are left
+        // to drain normally in the completion loop below.
+        if cancellation_token.is_cancelled() {
+            skipped_files += 1;
+            continue;
+        }
+
         // Clone Arc's for use in the spawned task
         let current_processor = Arc::clone(&processor);
         let tfp_clone = Arc::clone(&total_files_processed);
         let ee_clone = Arc::clone(&errors_encountered);
+        let reporter_clone = Arc::clone(&reporter);
         let input_file_path_clone = entry_path.clone();
         let output_file_path_clone = output_file_path.clone();
 
-        // Acquire a permit from the semaphore before spawning the task.
-        // This will pause if too many tasks are already running.
-        let permit = Arc::clone(&semaphore).acquire_owned().await;
+        // Acquire a permit from the semaphore before spawning the task. This will pause if
+        // too many tasks are already running, or return early if cancellation fires while
+        // waiting for a permit.
+        let permit = tokio::select! {
+            permit = Arc::clone(&semaphore).acquire_owned() => permit,
+            _ = cancellation_token.cancelled() => {
+                skipped_files += 1;
+                continue;
+            }
+        };
 
         // Spawn an asynchronous task for each file. This allows non-blocking I/O operations
         // to run concurrently.
@@ -227,33 +886,64 @@ pub async fn process_data(params: JobParameters) -> ProcessingResult {
             match current_processor.process_file(&input_file_path_clone, &output_file_path_clone).await {
                 Ok(_) => {
                     tfp_clone.fetch_add(1, Ordering::Relaxed);
+                    reporter_clone.on_completed(&input_file_path_clone, 0);
                 },
                 Err(e) => {
                     ee_clone.fetch_add(1, Ordering::Relaxed);
                     log::error!("Failed to process file {:?}: {}", input_file_path_clone, e);
+                    reporter_clone.on_error(&input_file_path_clone, &e);
                 }
             }
         });
         processing_futures.push_back(fut);
     }
 
-    // Await all spawned processing tasks. This loop will continue as tasks complete.
+    // Traversal is complete and every eligible file has been queued (nothing has been polled
+    // to completion yet, so the queue length is the number of spawned files, which excludes any
+    // discovered after cancellation), so the reporter can now switch from an indeterminate
+    // spinner to a determinate ratio bar.
+    reporter.on_discovery_complete(processing_futures.len() as u64);
+
+    // Await all spawned processing tasks. This loop will continue as tasks complete. We never
+    // abandon in-flight tasks on cancellation (they're already running and hold their permit),
+    // but checking the token here lets us log that a shutdown is in progress while we drain.
+    let mut cancellation_logged = false;
     while let Some(result) = processing_futures.next().await {
         // Handle potential panics or task failures from `tokio::spawn` (e.g., if a task itself panics)
         if let Err(join_error) = result {
             log::error!("A file processing task panicked or failed to join: {}", join_error);
             errors_encountered.fetch_add(1, Ordering::Relaxed);
         }
+        if !cancellation_logged && cancellation_token.is_cancelled() {
+            cancellation_logged = true;
+            log::info!("Cancellation requested for job {}; draining in-flight tasks", params.job_id);
+        }
     }
 
     let final_total = total_files_processed.load(Ordering::Relaxed);
     let final_errors = errors_encountered.load(Ordering::Relaxed);
+    let was_cancelled = cancellation_token.is_cancelled();
 
-    log::info!("Data processing job {} finished. Total files processed: {}, Errors encountered: {}", 
-               params.job_id, final_total, final_errors);
+    log::info!(
+        "Data processing job {} finished. Total files processed: {}, Errors encountered: {}, Cancelled: {}, Skipped: {}",
+        params.job_id, final_total, final_errors, was_cancelled, skipped_files
+    );
 
     ProcessingResult {
+        job_id: params.job_id.clone(),
+        file_path: params.input_path.clone(),
+        status: if was_cancelled { JobStatus::Cancelled } else { JobStatus::Completed },
+        // `JobParameters` (unlike `JobInput`) has no tenant concept yet; this pipeline is single-tenant.
+        tenant: String::new(),
+        message: None,
+        output_path: Some(params.output_path.clone()),
+        processing_time_ms: 0,
+        bytes_processed: 0,
+        custom_metrics: None,
         total_files_processed: final_total as u64,
         errors_encountered: final_errors as u64,
+        cancelled: was_cancelled,
+        skipped_files,
+        warnings: Vec::new(),
     }
 }
\ No newline at end of file
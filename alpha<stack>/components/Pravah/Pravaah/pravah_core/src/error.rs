@@ -1,5 +1,3 @@
-use pyo3::exceptions::{PyFileNotFoundError, PyIOError, PyRuntimeError, PyValueError};
-use pyo3::prelude::*;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -30,6 +28,12 @@ pub enum PravahError {
     #[error("Configuration error: {0}")]
     Configuration(String),
 
+    /// A caller-supplied value failed validation (e.g. a `Pipeline` nested inside another
+    /// `Pipeline`). Distinct from `Configuration`, which covers engine/environment setup rather
+    /// than the shape of a single request's data.
+    #[error("Validation error: {0}")]
+    Validation(String),
+
     /// An unsupported operation was attempted.
     #[error("Unsupported operation: {0}")]
     Unsupported(String),
@@ -42,6 +46,40 @@ pub enum PravahError {
     /// A catch-all for any other unexpected or unclassified errors.
     #[error("An unknown error occurred: {0}")]
     Unknown(String),
+
+    /// An external command spawned by `ProcessingAction::Shell` exited with a non-zero status.
+    #[error("Command failed for {}: exit code {:?}\nstderr: {}", .path.display(), .code, .stderr)]
+    ProcessError {
+        /// The input file being processed when the command failed.
+        path: PathBuf,
+        /// The process's exit code. `None` if the process was terminated by a signal.
+        code: Option<i32>,
+        /// Captured stderr output from the child process, for diagnostics.
+        stderr: String,
+    },
+
+    /// `ffprobe` (invoked by `ProcessingType::ExtractMediaMetadata`) either couldn't be run at
+    /// all, exited with a non-zero status, or produced output that isn't parseable as the JSON
+    /// `ffprobe` normally emits. A file that runs but reports no usable streams is not this variant — see
+    /// `engine::extract_media_metadata`, which surfaces that as a warning instead.
+    #[error("ffprobe failed for {}: {}", .path.display(), .message)]
+    Ffprobe {
+        /// The media file `ffprobe` was run against.
+        path: PathBuf,
+        /// A description of the invocation or parse failure.
+        message: String,
+    },
+
+    /// A credential, transport, or API failure against a remote object store (`s3://` URIs in
+    /// `JobInput.source_path`/`output_path`, gated behind the `object-storage` feature). Local
+    /// filesystem paths never produce this variant — see `models::is_object_store_uri`.
+    #[error("Object storage error for {uri}: {source}")]
+    ObjectStore {
+        /// The `s3://bucket/key` URI the operation was attempted against.
+        uri: String,
+        /// A description of the underlying credential/transport/API failure.
+        source: String,
+    },
 }
 
 /// A specialized `Result` type for Pravah operations.
@@ -50,30 +88,7 @@ pub enum PravahError {
 /// easier to propagate and manage `PravahError` instances.
 pub type PravahResult<T> = Result<T, PravahError>;
 
-/// Implements conversion from `PravahError` to PyO3's `PyErr`.
-///
-/// This allows Rust functions that return `PravahResult` to be exposed to Python
-/// via PyO3, and their errors will automatically be converted into appropriate
-/// Python exceptions, making error handling seamless on the Python side.
-impl From<PravahError> for PyErr {
-    fn from(err: PravahError) -> PyErr {
-        match err {
-            PravahError::Io(e) => PyIOError::new_err(e.to_string()),
-            PravahError::FileNotFound(path) => {
-                PyFileNotFoundError::new_err(format!("File not found: {}", path.display()))
-            }
-            PravahError::Processing { path, message } => {
-                let msg = if let Some(p) = path {
-                    format!("Processing error for '{}': {}", p.display(), message)
-                } else {
-                    format!("Processing error: {}", message)
-                };
-                PyValueError::new_err(msg)
-            }
-            PravahError::Configuration(msg) => PyValueError::new_err(format!("Configuration error: {}", msg)),
-            PravahError::Unsupported(msg) => PyRuntimeError::new_err(format!("Unsupported operation: {}", msg)),
-            PravahError::Internal(msg) => PyRuntimeError::new_err(format!("Internal Pravah error: {}", msg)),
-            PravahError::Unknown(msg) => PyRuntimeError::new_err(format!("An unknown error occurred in Pravah: {}", msg)),
-        }
-    }
-}
\ No newline at end of file
+// `impl From<PravahError> for PyErr` lives in `lib.rs` now, alongside the `create_exception!`
+// hierarchy (`PravahIoError`/`PravahParameterError`/`PravahProcessingError`/`PravahRuntimeError`)
+// it raises — keeping the Python-facing exception mapping next to the exception types themselves
+// rather than split across two modules.
\ No newline at end of file
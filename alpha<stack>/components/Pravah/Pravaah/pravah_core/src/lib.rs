@@ -1,19 +1,32 @@
 use pyo3::prelude::*;
+use pyo3::create_exception;
 use pyo3::exceptions::PyException;
 use pyo3::wrap_pyfunction;
+use std::error::Error as StdError;
 
+// `process_files_async` below needs the `tokio` backend of `pyo3-asyncio` added to
+// Cargo.toml (`pyo3-asyncio = { version = "0.20", features = ["tokio-runtime"] }`).
+// The shared runtime below needs `once_cell` added to Cargo.toml.
+use futures::stream::{FuturesOrdered, StreamExt};
+use once_cell::sync::OnceCell;
+use std::sync::Arc;
 use tokio::runtime::Runtime;
+use tokio_util::sync::CancellationToken;
 
 // Internal modules, declared here to be part of the crate
 mod engine;
 mod error;
 mod models;
+mod progress;
 
 // Import the core Rust types for use in PyO3 function signatures.
 // These types must derive `serde::Serialize` and `serde::Deserialize`
 // for PyO3's automatic conversion feature to work.
-use models::{JobParameters, ProcessingResult};
-use error::PravahCoreError;
+use models::{
+    ImageFormat, JobBatch, JobCheckpoint, JobInput, JobParameters, JobStatus, ProcessingAction, ProcessingResult,
+    ProcessingType, ProgressUpdate,
+};
+use error::PravahError;
 
 // Define a custom Python exception type that extends Python's base Exception.
 // This allows specific Rust errors to map to a well-defined Python exception,
@@ -38,17 +51,90 @@ impl PravahCorePyException {
     }
 }
 
-// Implement the `From` trait to convert our `PravahCoreError` (Rust enum)
-// into a `PyErr` (PyO3's error type). This is crucial for seamless
-// error propagation from the Rust core to the Python application.
-impl From<PravahCoreError> for PyErr {
-    fn from(err: PravahCoreError) -> PyErr {
-        // Use our custom Python exception type to wrap the Rust error message.
-        // The `format!("{}", err)` uses the `Display` implementation of `PravahCoreError`.
-        PravahCorePyException::new_err(format!("{}", err))
+// A Python exception hierarchy under the existing `PravahCorePyException` base, so callers can
+// `except PravahIoError` separately from `except PravahParameterError` instead of string-matching
+// one flat exception.
+create_exception!(pravah_core, PravahIoError, PravahCorePyException);
+create_exception!(pravah_core, PravahParameterError, PravahCorePyException);
+create_exception!(pravah_core, PravahProcessingError, PravahCorePyException);
+create_exception!(pravah_core, PravahRuntimeError, PravahCorePyException);
+
+// Implement the `From` trait to convert our `PravahError` (Rust enum) into a `PyErr`, raising the
+// exception subclass matching the variant rather than the flat base class.
+impl From<PravahError> for PyErr {
+    fn from(err: PravahError) -> PyErr {
+        let message = err.to_string();
+        let py_err = match &err {
+            PravahError::Io(_) | PravahError::FileNotFound(_) | PravahError::ObjectStore { .. } => {
+                PravahIoError::new_err(message)
+            }
+            PravahError::Configuration(_) | PravahError::Validation(_) => PravahParameterError::new_err(message),
+            PravahError::Processing { .. } | PravahError::ProcessError { .. } | PravahError::Ffprobe { .. } => {
+                PravahProcessingError::new_err(message)
+            }
+            PravahError::Unsupported(_) | PravahError::Internal(_) | PravahError::Unknown(_) => {
+                PravahRuntimeError::new_err(message)
+            }
+        };
+
+        // Preserve `err`'s `.source()` chain (e.g. the underlying `std::io::Error` behind
+        // `PravahError::Io`) as the Python exception's `__cause__`, so a traceback shows the root
+        // cause rather than just `PravahError`'s own top-level `Display` message.
+        let mut chain = Vec::new();
+        let mut source = StdError::source(&err);
+        while let Some(s) = source {
+            chain.push(s.to_string());
+            source = s.source();
+        }
+        if !chain.is_empty() {
+            Python::with_gil(|py| py_err.set_cause(py, Some(PyException::new_err(chain.join(": ")))));
+        }
+
+        py_err
     }
 }
 
+/// The single multithreaded Tokio runtime shared by every blocking entry point in this module.
+/// Built lazily on first use (or eagerly via [`configure_runtime`]) rather than once per call, so
+/// a host serving many jobs isn't paying thread/epoll setup-and-teardown cost on every call.
+static RUNTIME: OnceCell<Runtime> = OnceCell::new();
+
+/// Returns the shared runtime, building it with a default worker count on first use.
+fn shared_runtime() -> Result<&'static Runtime, PravahError> {
+    RUNTIME
+        .get_or_try_init(|| build_runtime(default_worker_threads()))
+        .map_err(|e| PravahError::Internal(format!("Failed to initialize the shared Tokio runtime: {}", e)))
+}
+
+fn default_worker_threads() -> usize {
+    std::env::var("PRAVAH_RUNTIME_WORKER_THREADS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4))
+}
+
+fn build_runtime(worker_threads: usize) -> std::io::Result<Runtime> {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(worker_threads)
+        .enable_all()
+        .build()
+}
+
+/// Tunes the worker-thread count of the shared Tokio runtime used by [`py_process_files`] and
+/// [`py_process_files_async`]. Must be called before the runtime is first used — once the shared
+/// runtime has been built, its thread pool is fixed for the lifetime of the process.
+#[pyfunction]
+fn configure_runtime(worker_threads: usize) -> PyResult<()> {
+    let rt = build_runtime(worker_threads)
+        .map_err(|e| PravahError::Internal(format!("Failed to build the configured Tokio runtime: {}", e)))?;
+
+    RUNTIME.set(rt).map_err(|_| {
+        PyErr::from(PravahError::Internal(
+            "configure_runtime() must be called before the shared runtime is first used".to_string(),
+        ))
+    })
+}
+
 /// A Python function that initiates a high-performance file processing job in the Pravah Rust core.
 ///
 /// This function serves as the primary entry point from Python to the Rust engine.
@@ -62,47 +148,259 @@ impl From<PravahCoreError> for PyErr {
 ///     params (models.JobParameters): An object (converted from a Python dictionary or Pydantic model)
 ///                                    containing the parameters for the processing job, including
 ///                                    input/output paths, processing options, and job metadata.
+///     progress_callback: An optional callable invoked periodically with a dict
+///                        `{files_processed, bytes_processed, percent}` as the job runs (e.g. a
+///                        Celery task updating job state, or a tqdm bar). The GIL is only held
+///                        for the duration of each call. If the callback returns `False` or
+///                        raises, the job is cooperatively cancelled: in-flight files are
+///                        allowed to drain and the returned `ProcessingResult` has
+///                        `cancelled = True` rather than raising.
 ///
 /// Returns:
 ///     models.ProcessingResult: An object (converted to a Python dictionary) containing the
 ///                              comprehensive summary of the completed processing job, including
 ///                              overall status, counts of files scanned and processed, and
 ///                              detailed results for individual files.
-///
-/// Raises:
-///     PravahCorePyException: If any error occurs during the processing within the Rust core,
-///                            such as I/O errors (e.g., file not found, permission denied),
-///                            invalid input parameters, or issues with the underlying Tokio runtime.
 #[pyfunction]
 #[pyo3(name = "process_files")] // Exposes the function to Python under this name
-fn py_process_files(_py: Python, params: JobParameters) -> PyResult<ProcessingResult> {
-    // Initialize a new Tokio runtime.
-    // This runtime will execute all asynchronous Rust code within this function call.
-    // `block_on` will block the current thread until the async operation completes,
-    // which is suitable for long-running batch processing jobs.
-    let rt = Runtime::new().map_err(|e| {
-        // Convert `std::io::Error` (from `Runtime::new`) into our custom `PravahCoreError`
-        // and then into a `PyErr` (PravahCorePyException).
-        PravahCoreError::TokioRuntime(format!("Failed to create Tokio runtime: {}", e))
-    })?;
-
-    // Block on the asynchronous processing function provided by the `engine` module.
-    // This is where the core file scanning, parallel processing, and data manipulation
-    // logic will be executed.
-    let result = rt.block_on(async {
-        engine::process_files_async(params).await
-    });
+#[pyo3(signature = (params, progress_callback = None))]
+fn py_process_files(py: Python, mut params: JobParameters, progress_callback: Option<PyObject>) -> PyResult<ProcessingResult> {
+    let rt = shared_runtime()?;
+
+    let cancellation_token = Arc::new(CancellationToken::new());
+    if let Some(callback) = progress_callback {
+        params.progress_reporter = Some(Arc::new(progress::PyCallbackProgressReporter::new(
+            params.job_id.clone(),
+            callback,
+            Arc::clone(&cancellation_token),
+        )));
+    }
+    params.cancellation_token = Some(cancellation_token);
+
+    // Release the GIL for the duration of the blocking call, so other Python threads keep
+    // running while Rust does file I/O and CPU-bound processing; `progress_callback` above is
+    // the only point that re-acquires it. `process_data` is infallible — cancellation and
+    // per-file errors are reported through the returned `ProcessingResult`, not a `PyErr`.
+    let result = py.allow_threads(|| rt.block_on(async { engine::process_data(params).await }));
+
+    Ok(result)
+}
+
+/// Async-coroutine counterpart to [`py_process_files`], for callers running inside an existing
+/// `asyncio` event loop where blocking the calling thread on `block_on` would stall the whole
+/// loop. Returns a Python awaitable backed by the same `engine` pipeline, via `pyo3-asyncio`'s
+/// Tokio integration, so a host can schedule many jobs concurrently instead of serializing them
+/// behind one OS thread per call. Accepts the same `progress_callback` as `py_process_files`.
+#[pyfunction]
+#[pyo3(name = "process_files_async")]
+#[pyo3(signature = (params, progress_callback = None))]
+fn py_process_files_async(py: Python, mut params: JobParameters, progress_callback: Option<PyObject>) -> PyResult<&PyAny> {
+    let cancellation_token = Arc::new(CancellationToken::new());
+    if let Some(callback) = progress_callback {
+        params.progress_reporter = Some(Arc::new(progress::PyCallbackProgressReporter::new(
+            params.job_id.clone(),
+            callback,
+            Arc::clone(&cancellation_token),
+        )));
+    }
+    params.cancellation_token = Some(cancellation_token);
 
-    // Handle any `PravahCoreError` returned by the Rust engine.
-    // The `map_err(PyErr::from)` automatically converts the Rust error into a `PyErr`
-    // (using our `From<PravahCoreError> for PyErr` implementation) and propagates it
-    // as a Python exception.
-    let rust_result = result.map_err(PyErr::from)?;
+    pyo3_asyncio::tokio::future_into_py(py, async move { Ok(engine::process_data(params).await) })
+}
 
-    // Return the `ProcessingResult`. PyO3, with the `serde` feature enabled, will
-    // automatically serialize this Rust struct into a Python dictionary, assuming
-    // `ProcessingResult` and its nested types implement `serde::Serialize`.
-    Ok(rust_result)
+/// Everything `prepare_job` resolves up front for a single `JobInput`, before `process_data` is
+/// actually run — split out so [`submit_batch`] can prepare every job in a batch (and surface any
+/// per-job setup error as a `FAILED` result) before releasing the GIL once for the whole batch,
+/// rather than once per job.
+struct PreparedJob {
+    job: JobInput,
+    params: JobParameters,
+    checkpoint_path: Option<std::path::PathBuf>,
+    previously_completed: Vec<String>,
+    checkpoint_reporter: Arc<progress::CheckpointingProgressReporter>,
+}
+
+/// Resolves `job` into a [`PreparedJob`]: loads an existing checkpoint at `job.resume_from` (if
+/// any), converts `job.processing_type` into a `ProcessingAction`, and wires up the progress
+/// reporter `progress_callback` (if given) feeds into. Shared setup behind both `submit_job` and
+/// `submit_batch`.
+///
+/// # Errors
+/// Returns `PravahRuntimeError` if `job.processing_type` has no `ProcessingAction` equivalent
+/// yet (see `TryFrom<ProcessingType> for ProcessingAction`, which returns `PravahError::Unsupported`),
+/// or `PravahIoError`/`PravahRuntimeError` if an existing checkpoint at `resume_from` can't be loaded.
+fn prepare_job(job: JobInput, progress_callback: Option<PyObject>) -> PyResult<PreparedJob> {
+    let checkpoint_path = job.resume_from.as_ref().map(std::path::PathBuf::from);
+    let previously_completed = match &checkpoint_path {
+        Some(path) if path.exists() => JobCheckpoint::load(path)?.completed_files,
+        _ => Vec::new(),
+    };
+
+    let processing_action = ProcessingAction::try_from(job.processing_type.clone())?;
+
+    if !job.file_patterns.is_empty() {
+        log::warn!(
+            "job {} specifies file_patterns {:?}, which aren't applied yet (JobParameters.file_filters only supports extension allow/deny lists)",
+            job.job_id, job.file_patterns
+        );
+    }
+
+    let mut params = JobParameters {
+        job_id: job.job_id.clone(),
+        input_path: job.source_path.clone(),
+        output_path: job.output_path.clone().unwrap_or_else(|| job.source_path.clone()),
+        processing_action,
+        file_filters: models::FileFilters::default(),
+        skip_relative_paths: previously_completed.iter().cloned().collect(),
+        progress_reporter: None,
+        cancellation_token: None,
+    };
+
+    let cancellation_token = Arc::new(CancellationToken::new());
+    let inner_reporter: Arc<dyn progress::ProgressReporter> = match progress_callback {
+        Some(callback) => Arc::new(progress::PyCallbackProgressReporter::new(
+            job.job_id.clone(),
+            callback,
+            Arc::clone(&cancellation_token),
+        )),
+        None => Arc::new(progress::TerminalProgressReporter::new()),
+    };
+    let checkpoint_reporter = Arc::new(progress::CheckpointingProgressReporter::new(
+        inner_reporter,
+        std::path::PathBuf::from(&job.source_path),
+    ));
+    params.progress_reporter = Some(checkpoint_reporter.clone());
+    params.cancellation_token = Some(cancellation_token);
+
+    Ok(PreparedJob {
+        job,
+        params,
+        checkpoint_path,
+        previously_completed,
+        checkpoint_reporter,
+    })
+}
+
+/// Stamps `result.tenant` from `prepared.job` and, if `prepared.job.resume_from` was set, writes
+/// an updated [`JobCheckpoint`] back to that path (completed files from this run folded into
+/// `prepared.previously_completed`). Shared finalization behind both `submit_job` and
+/// `submit_batch`.
+fn finalize_job(prepared: PreparedJob, mut result: ProcessingResult) -> ProcessingResult {
+    result.tenant = prepared.job.tenant.clone();
+
+    if let Some(path) = prepared.checkpoint_path {
+        let mut completed_files = prepared.previously_completed;
+        completed_files.extend(prepared.checkpoint_reporter.completed_relative_paths());
+
+        let checkpoint = JobCheckpoint {
+            job_id: prepared.job.job_id.clone(),
+            completed_files,
+            pending_files: Vec::new(),
+            job_input: prepared.job,
+        };
+        if let Err(e) = checkpoint.save(&path) {
+            log::warn!("failed to save checkpoint to {:?}: {}", path, e);
+        }
+    }
+
+    result
+}
+
+/// Runs a single `JobInput` through `engine::process_data`, bridging the Python-facing job
+/// descriptor model (`JobInput`/`ProcessingType`) to the engine's own model
+/// (`JobParameters`/`ProcessingAction`) via `TryFrom<ProcessingType> for ProcessingAction`.
+///
+/// If `job.resume_from` is set, a [`JobCheckpoint`] previously written to that path is loaded
+/// first and its `completed_files` are skipped during traversal; once the job finishes, an
+/// updated checkpoint (completed files from this run folded in) is written back to the same
+/// path, so a crashed or paused job can resume by calling `submit_job` again with the same
+/// `resume_from`. A caller that never sets `resume_from` gets no checkpointing at all — there
+/// is no path to persist one to.
+///
+/// `job.file_patterns` (glob patterns) aren't applied yet: `JobParameters.file_filters` only
+/// supports extension allow/deny lists, not globs, so this doesn't silently pretend to filter
+/// by them.
+///
+/// # Errors
+/// Returns `PravahRuntimeError` if `job.processing_type` has no `ProcessingAction` equivalent
+/// yet (see `TryFrom<ProcessingType> for ProcessingAction`, which returns `PravahError::Unsupported`),
+/// or `PravahIoError`/`PravahRuntimeError` if an existing checkpoint at `resume_from` can't be loaded.
+#[pyfunction]
+#[pyo3(signature = (job, progress_callback = None))]
+fn submit_job(py: Python, job: JobInput, progress_callback: Option<PyObject>) -> PyResult<ProcessingResult> {
+    let rt = shared_runtime()?;
+    let prepared = prepare_job(job, progress_callback)?;
+    let params = prepared.params.clone();
+
+    let result = py.allow_threads(|| rt.block_on(async { engine::process_data(params).await }));
+    Ok(finalize_job(prepared, result))
+}
+
+/// Submits every `JobInput` in `batch.jobs` through `submit_job`'s per-job pipeline (checkpoint
+/// load/save, `ProcessingType` -> `ProcessingAction` conversion, progress reporting), running them
+/// concurrently via a `FuturesOrdered` and releasing the GIL once for the whole batch rather than
+/// once per job. `progress_callback`, if given, is shared across every job in the batch; each
+/// `ProgressUpdate` it receives still carries that job's own `job_id` (see
+/// `PyCallbackProgressReporter`), so a caller can attribute updates back to the right job.
+///
+/// A job that fails [`prepare_job`] (an unsupported `processing_type`, or a checkpoint at
+/// `resume_from` that can't be loaded) does not abort the rest of the batch: it's reported back
+/// as a `ProcessingResult` with `status = FAILED` and `message` set to the error, in the same
+/// position it occupied in `batch.jobs`, mirroring how `engine::process_data` itself reports
+/// per-file failures without raising.
+#[pyfunction]
+#[pyo3(signature = (batch, progress_callback = None))]
+fn submit_batch(py: Python, batch: JobBatch, progress_callback: Option<PyObject>) -> PyResult<Vec<ProcessingResult>> {
+    let rt = shared_runtime()?;
+
+    let mut prepared_or_failed = Vec::with_capacity(batch.jobs.len());
+    for job in batch.jobs {
+        let job_id = job.job_id.clone();
+        let tenant = job.tenant.clone();
+        match prepare_job(job, progress_callback.clone()) {
+            Ok(prepared) => prepared_or_failed.push(Ok(prepared)),
+            Err(e) => {
+                log::warn!("submit_batch: job {} failed to prepare: {}", job_id, e);
+                prepared_or_failed.push(Err(ProcessingResult::new(
+                    job_id,
+                    String::new(),
+                    JobStatus::Failed,
+                    tenant,
+                    Some(e.to_string()),
+                    None,
+                    0,
+                    0,
+                    None,
+                    0,
+                    0,
+                    false,
+                    0,
+                    Vec::new(),
+                )));
+            }
+        }
+    }
+
+    let results = py.allow_threads(|| {
+        rt.block_on(async {
+            let mut futures = FuturesOrdered::new();
+            for prepared_or_failed in prepared_or_failed {
+                futures.push_back(async move {
+                    match prepared_or_failed {
+                        Ok(prepared) => {
+                            let params = prepared.params.clone();
+                            let result = engine::process_data(params).await;
+                            finalize_job(prepared, result)
+                        }
+                        Err(failed_result) => failed_result,
+                    }
+                });
+            }
+            futures.collect::<Vec<_>>().await
+        })
+    });
+
+    Ok(results)
 }
 
 /// Initializes the `_pravah_core` Rust library as a Python module.
@@ -121,10 +419,28 @@ fn _pravah_core(_py: Python, m: &PyModule) -> PyResult<()> {
     // Python code can then `from pravah_core import PravahCorePyException`
     // and catch it.
     m.add_class::<PravahCorePyException>()?;
+    m.add("PravahIoError", _py.get_type::<PravahIoError>())?;
+    m.add("PravahParameterError", _py.get_type::<PravahParameterError>())?;
+    m.add("PravahProcessingError", _py.get_type::<PravahProcessingError>())?;
+    m.add("PravahRuntimeError", _py.get_type::<PravahRuntimeError>())?;
+
+    // Job descriptor types that make up the `JobInput`/`ProcessingType` model `submit_job`
+    // accepts, so Python callers can actually import and construct them.
+    m.add_class::<ImageFormat>()?;
+    m.add_class::<ProcessingType>()?;
+    m.add_class::<JobStatus>()?;
+    m.add_class::<JobInput>()?;
+    m.add_class::<JobBatch>()?;
+    m.add_class::<ProcessingResult>()?;
+    m.add_class::<ProgressUpdate>()?;
 
     // Add the `py_process_files` function to the module.
     // `wrap_pyfunction!` handles the boilerplate of creating a Python callable.
     m.add_function(wrap_pyfunction!(py_process_files, m)?)?;
+    m.add_function(wrap_pyfunction!(py_process_files_async, m)?)?;
+    m.add_function(wrap_pyfunction!(configure_runtime, m)?)?;
+    m.add_function(wrap_pyfunction!(submit_job, m)?)?;
+    m.add_function(wrap_pyfunction!(submit_batch, m)?)?;
 
     // Set a docstring for the Python module itself.
     m.setattr("__doc__", "High-performance Rust core for Pravah file and data processing.")?;
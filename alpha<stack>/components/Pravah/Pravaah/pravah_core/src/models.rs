@@ -1,7 +1,148 @@
-```rust
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::error::PravahError;
+use crate::progress::ProgressReporter;
+use tokio_util::sync::CancellationToken;
+
+// --- process_data job types ---
+//
+// These types describe a single `process_data` invocation (see `engine.rs`). They are
+// (de)serialized to/from Python via `serde`, the same mechanism used for `ProcessingResult`
+// below, so no `#[pyclass]` derive is required here.
+
+/// Compression codec used by `ProcessingAction::Compress`/`Decompress`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Codec {
+    Gzip,
+    Bzip2,
+    Zstd,
+}
+
+impl Codec {
+    /// The file extension (without the leading dot) this codec's archives conventionally use.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Codec::Gzip => "gz",
+            Codec::Bzip2 => "bz2",
+            Codec::Zstd => "zst",
+        }
+    }
+
+    /// Infers a codec from a file extension (without the leading dot), if recognized.
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "gz" => Some(Codec::Gzip),
+            "bz2" => Some(Codec::Bzip2),
+            "zst" => Some(Codec::Zstd),
+            _ => None,
+        }
+    }
+}
+
+/// Defines the type of processing `process_data` performs on each discovered file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProcessingAction {
+    /// Copies the file byte-for-byte.
+    Copy,
+    /// Writes out only the first `num_lines` lines of a text file.
+    ExtractFirstLines { num_lines: usize },
+    /// Streams the file through `codec` at the given compression `level`, appending the
+    /// codec's extension to the output path.
+    Compress { codec: Codec, level: u32 },
+    /// Streams the file through a decompression codec, stripping a recognized compression
+    /// extension from the output path. When `codec` is `None` the codec is inferred from the
+    /// input file's extension.
+    Decompress { codec: Option<Codec> },
+    /// Pipes the file through an external command: the file's bytes are written to the
+    /// child's stdin, and its stdout becomes the output file. Lets users plug in arbitrary
+    /// transforms (image converters, `jq`, custom scripts) without recompiling.
+    Shell { command: String, args: Vec<String> },
+    /// Runs `ffprobe` against the file and writes the extracted metadata (plus any warnings)
+    /// to the output path as JSON — see `engine::extract_media_metadata`. Reachable from
+    /// Python via `ProcessingType::ExtractMediaMetadata` (see `TryFrom<ProcessingType>` below).
+    ExtractMediaMetadata,
+    /// Converts an image to `target`, optionally downscaling to fit within `max_dimension` on
+    /// its longest side. Reachable from Python via `ProcessingType::ConvertImage`.
+    ConvertImage {
+        target: ImageFormat,
+        max_dimension: Option<u32>,
+    },
+    /// Chains `steps`, feeding each step's output forward as the next step's input, writing
+    /// only the final step's output to the job's output path. Reachable from Python via
+    /// `ProcessingType::Pipeline`.
+    Pipeline { steps: Vec<ProcessingAction> },
+}
+
+/// Converts the Python-facing job descriptor (`ProcessingType`) into the action `process_data`
+/// actually dispatches on. `ExtractHeaders`, `CompressFile`, `ResizeImage`, and `CustomScript`
+/// predate this conversion and have no equivalent `ProcessingAction` yet, so they're rejected
+/// with a clear message rather than silently mapped to something approximate.
+impl TryFrom<ProcessingType> for ProcessingAction {
+    type Error = PravahError;
+
+    fn try_from(value: ProcessingType) -> Result<Self, Self::Error> {
+        match value {
+            ProcessingType::ExtractMediaMetadata => Ok(ProcessingAction::ExtractMediaMetadata),
+            ProcessingType::ConvertImage { target, max_dimension } => {
+                Ok(ProcessingAction::ConvertImage { target, max_dimension })
+            }
+            ProcessingType::Pipeline { steps } => {
+                let steps = steps
+                    .into_iter()
+                    .map(ProcessingAction::try_from)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(ProcessingAction::Pipeline { steps })
+            }
+            other @ (ProcessingType::ExtractHeaders
+            | ProcessingType::CompressFile
+            | ProcessingType::ResizeImage { .. }
+            | ProcessingType::CustomScript { .. }) => Err(PravahError::Unsupported(format!(
+                "{:?} predates process_data's ProcessingAction integration and isn't wired to the engine yet",
+                other
+            ))),
+        }
+    }
+}
+
+/// Extension-based allow/deny lists applied to discovered files before processing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileFilters {
+    pub include_extensions: Option<Vec<String>>,
+    pub exclude_extensions: Option<Vec<String>>,
+}
+
+/// Parameters for a single `process_data` job, received from the Python layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobParameters {
+    pub job_id: String,
+    pub input_path: String,
+    pub output_path: String,
+    pub processing_action: ProcessingAction,
+    #[serde(default)]
+    pub file_filters: FileFilters,
+    /// Relative paths (relative to `input_path`) to skip during traversal, because a prior run
+    /// already completed them — see `JobCheckpoint` and `lib::submit_job`, which populates this
+    /// from `JobInput.resume_from` before calling `process_data`.
+    #[serde(default)]
+    pub skip_relative_paths: std::collections::HashSet<String>,
+    /// Optional callback sink for live progress updates. It is never populated by
+    /// deserializing `JobParameters` from a Python dict; the Python bindings set it after
+    /// deserialization when a `progress_callback` is given, and `engine::process_data` falls
+    /// back to a terminal progress bar when it is unset.
+    #[serde(skip)]
+    pub progress_reporter: Option<Arc<dyn ProgressReporter>>,
+    /// Optional cooperative cancellation signal. When the token is cancelled, `process_data`
+    /// stops spawning new file tasks, lets in-flight ones drain, and returns early with
+    /// `ProcessingResult::cancelled` set. Like `progress_reporter`, this is skipped by
+    /// deserialization; the Python bindings install a token that is cancelled when their
+    /// `progress_callback` asks to stop. No signal handler cancels it.
+    #[serde(skip)]
+    pub cancellation_token: Option<Arc<CancellationToken>>,
+}
 
 // --- Enums ---
 
@@ -20,6 +161,26 @@ pub enum ImageFormat {
     /// WebP image format.
     #[pyo3(name = "WEBP")]
     Webp,
+    /// AVIF image format.
+    #[pyo3(name = "AVIF")]
+    Avif,
+    /// GIF image format.
+    #[pyo3(name = "GIF")]
+    Gif,
+    /// TIFF image format.
+    #[pyo3(name = "TIFF")]
+    Tiff,
+    /// BMP image format.
+    #[pyo3(name = "BMP")]
+    Bmp,
+    /// HEIF/HEIC image format.
+    #[pyo3(name = "HEIF")]
+    Heif,
+    /// SVG vector image format. Only valid as a `ConvertImage` source, never as a `target` —
+    /// rasterizing *to* SVG isn't a meaningful operation, so `ProcessingType::new_convert_image`
+    /// rejects it there.
+    #[pyo3(name = "SVG")]
+    Svg,
 }
 
 #[pymethods]
@@ -50,6 +211,59 @@ impl ImageFormat {
     }
 }
 
+impl ImageFormat {
+    /// The file extension (without the leading dot) this format conventionally uses.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Png => "png",
+            ImageFormat::Webp => "webp",
+            ImageFormat::Avif => "avif",
+            ImageFormat::Gif => "gif",
+            ImageFormat::Tiff => "tiff",
+            ImageFormat::Bmp => "bmp",
+            ImageFormat::Heif => "heif",
+            ImageFormat::Svg => "svg",
+        }
+    }
+
+    /// Infers an image format from a file extension (without the leading dot), if recognized.
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_ascii_lowercase().as_str() {
+            "jpg" | "jpeg" => Some(ImageFormat::Jpeg),
+            "png" => Some(ImageFormat::Png),
+            "webp" => Some(ImageFormat::Webp),
+            "avif" => Some(ImageFormat::Avif),
+            "gif" => Some(ImageFormat::Gif),
+            "tif" | "tiff" => Some(ImageFormat::Tiff),
+            "bmp" => Some(ImageFormat::Bmp),
+            "heif" | "heic" => Some(ImageFormat::Heif),
+            "svg" => Some(ImageFormat::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// All file extensions (without the leading dot) that `ProcessingType::ConvertImage` can read
+/// as a source or write as a `target`, so a Python caller can validate a path against what the
+/// engine supports before submitting a job.
+pub fn supported_image_extensions() -> Vec<String> {
+    [
+        ImageFormat::Jpeg,
+        ImageFormat::Png,
+        ImageFormat::Webp,
+        ImageFormat::Avif,
+        ImageFormat::Gif,
+        ImageFormat::Tiff,
+        ImageFormat::Bmp,
+        ImageFormat::Heif,
+        ImageFormat::Svg,
+    ]
+    .iter()
+    .map(|format| format.extension().to_string())
+    .collect()
+}
+
 /// Defines the type of processing to be performed on files.
 /// This enum is tagged for serialization/deserialization, meaning its JSON/Python dictionary
 /// representation will include a "type" field to distinguish variants, and a "data" field
@@ -79,6 +293,38 @@ pub enum ProcessingType {
         #[pyo3(get, set)]
         args: Vec<String>,
     },
+    /// Chains multiple processing types, applying them in order to the same file and feeding
+    /// each step's output forward as the next step's input (e.g. resize then compress in one
+    /// pass, instead of two separate jobs over the same files).
+    ///
+    /// Converts to `engine::PipelineProcessor` via `TryFrom<ProcessingType> for
+    /// ProcessingAction`. A failure in any step short-circuits the file with an error naming
+    /// the failing step's index; per-step timing is logged at `debug` rather than surfaced in
+    /// `ProcessingResult.custom_metrics`, since `ProcessingResult` reports a whole job, not a
+    /// single file. `steps` must not itself contain a `Pipeline` — see `JobInput::new`, which
+    /// rejects that at construction time.
+    Pipeline {
+        #[pyo3(get, set)]
+        steps: Vec<ProcessingType>,
+    },
+    /// Runs `ffprobe` against an audio/video/image file and writes a per-file JSON output with
+    /// keys like `duration_seconds`, `codec`, `width`, `height`, `bitrate`, and `frame_rate` —
+    /// see `engine::extract_media_metadata`. A file with no usable streams is not a failure: its
+    /// warning is logged and written into that JSON. `ProcessingResult.custom_metrics` and
+    /// `ProcessingResult.warnings` are not filled from this metadata.
+    ExtractMediaMetadata,
+    /// Converts an image to `target` format, optionally downscaling while preserving aspect
+    /// ratio. When `max_dimension` is set, a raster source is scaled so its longest side is no
+    /// greater than it — never upscaling a smaller raster source. An `Svg` source is rasterized
+    /// first, at a scale factor of `target longest side / source longest side` (which enlarges
+    /// an SVG smaller than `max_dimension`), before encoding to `target`. `target` itself must never be `Svg` — see `new_convert_image`,
+    /// which rejects that at construction time.
+    ConvertImage {
+        #[pyo3(get, set)]
+        target: ImageFormat,
+        #[pyo3(get, set)]
+        max_dimension: Option<u32>,
+    },
 }
 
 #[pymethods]
@@ -118,12 +364,74 @@ impl ProcessingType {
         ProcessingType::CustomScript { script_path, args }
     }
 
+    /// Creates a new `Pipeline` processing type instance from an ordered list of steps.
+    ///
+    /// # Arguments
+    /// * `steps` - The processing types to apply in order, each fed the previous step's output.
+    ///
+    /// # Errors
+    /// Returns `PravahParameterError` (via `PravahError::Validation`) if any of `steps` is
+    /// itself a `Pipeline`, since nesting pipelines has no well-defined "previous step's
+    /// output" and would let a caller build an arbitrarily deep chain.
+    #[new]
+    #[pyo3(signature = (steps))]
+    fn new_pipeline(steps: Vec<ProcessingType>) -> PyResult<Self> {
+        reject_nested_pipeline(&steps)?;
+        Ok(ProcessingType::Pipeline { steps })
+    }
+
+    /// Creates a new `ExtractMediaMetadata` processing type instance.
+    #[new]
+    fn new_extract_media_metadata() -> Self {
+        ProcessingType::ExtractMediaMetadata
+    }
+
+    /// Creates a new `ConvertImage` processing type instance.
+    ///
+    /// # Arguments
+    /// * `target` - The image format to convert to. Must not be `SVG`.
+    /// * `max_dimension` - Optional cap on the longest output side; the image is downscaled
+    ///   (never upscaled) to fit within it, preserving aspect ratio.
+    ///
+    /// # Errors
+    /// Returns `PravahParameterError` (via `PravahError::Validation`) if `target` is `SVG`,
+    /// since rasterizing an image *to* SVG isn't a supported operation.
+    #[new]
+    #[pyo3(signature = (target, max_dimension = None))]
+    fn new_convert_image(target: ImageFormat, max_dimension: Option<u32>) -> PyResult<Self> {
+        reject_svg_target(&target)?;
+        Ok(ProcessingType::ConvertImage { target, max_dimension })
+    }
+
     /// Provides a detailed string representation for Python's repr().
     fn __repr__(&self) -> String {
         format!("{:?}", self)
     }
 }
 
+/// Rejects a `Pipeline`'s `steps` list if any step is itself a `Pipeline`, guarding against
+/// unbounded nesting since a pipeline executor applies steps in a flat sequence rather than
+/// recursively.
+fn reject_nested_pipeline(steps: &[ProcessingType]) -> Result<(), PravahError> {
+    if steps.iter().any(|step| matches!(step, ProcessingType::Pipeline { .. })) {
+        return Err(PravahError::Validation(
+            "Pipeline steps must not themselves be a Pipeline".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Rejects `target = Svg` for `ProcessingType::ConvertImage`, since rasterizing an image *to*
+/// a vector format has no well-defined meaning — `Svg` is only ever a valid conversion source.
+fn reject_svg_target(target: &ImageFormat) -> Result<(), PravahError> {
+    if *target == ImageFormat::Svg {
+        return Err(PravahError::Validation(
+            "ConvertImage target must not be SVG; rasterizing to a vector format isn't supported".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 /// Represents the current status of a processing job or a single file's processing outcome.
 /// Exposed to Python for clear status tracking.
 #[pyclass]
@@ -145,6 +453,16 @@ pub enum JobStatus {
     /// The job/file processing was cancelled.
     #[pyo3(name = "CANCELLED")]
     Cancelled,
+    /// The job has been deliberately paused by the caller and can be resumed later from a
+    /// [`JobCheckpoint`]. Unlike `Suspended`, a `Paused` job is expected to resume imminently
+    /// (e.g. the process staying alive while waiting on a rate limit).
+    #[pyo3(name = "PAUSED")]
+    Paused,
+    /// The job was interrupted (a crash, a restart) rather than deliberately paused, and its
+    /// only remaining record is the checkpoint written to disk. Reloading that checkpoint via
+    /// `JobInput.resume_from` moves it back to `Running`, skipping `completed_files`.
+    #[pyo3(name = "SUSPENDED")]
+    Suspended,
 }
 
 #[pymethods]
@@ -174,6 +492,16 @@ impl JobStatus {
     fn new_cancelled() -> Self {
         JobStatus::Cancelled
     }
+    /// Creates a new `Paused` job status.
+    #[new]
+    fn new_paused() -> Self {
+        JobStatus::Paused
+    }
+    /// Creates a new `Suspended` job status.
+    #[new]
+    fn new_suspended() -> Self {
+        JobStatus::Suspended
+    }
 
     /// Provides a string representation for the JobStatus, typically its uppercase name.
     fn __str__(&self) -> String {
@@ -187,6 +515,15 @@ impl JobStatus {
 
 // --- Structs ---
 
+/// Reports whether `path` is a remote object-storage URI (`s3://bucket/key`) rather than a
+/// local filesystem path, so the engine can dispatch `JobInput.source_path`/`output_path` to
+/// the right backend. Only the `s3://` scheme is recognized today; S3-compatible services
+/// reached through a custom endpoint (e.g. MinIO) still use this scheme, with the endpoint
+/// itself supplied via `JobInput.storage_config`.
+pub fn is_object_store_uri(path: &str) -> bool {
+    path.starts_with("s3://")
+}
+
 /// Represents the input parameters for a new processing job, received from the Python layer.
 /// This struct is a PyO3 class, making it directly usable and constructible from Python.
 #[pyclass]
@@ -205,6 +542,11 @@ pub struct JobInput {
     /// A list of glob patterns (e.g., "*.csv", "*.log") to filter files for processing.
     #[pyo3(get)]
     pub file_patterns: Vec<String>,
+    /// The owner this job is billed and reported against. Lets a `JobBatch` group its
+    /// `ProcessingResult`s by owner when several tenants' work is fanned out through one
+    /// engine instance.
+    #[pyo3(get)]
+    pub tenant: String,
     /// The specific type of processing to apply to the files.
     #[pyo3(get)]
     pub processing_type: ProcessingType,
@@ -212,6 +554,20 @@ pub struct JobInput {
     /// If None, the engine will determine an optimal concurrency level.
     #[pyo3(get)]
     pub max_concurrency: Option<usize>,
+    /// An optional path to a [`JobCheckpoint`] written by a previous, crashed or paused run of
+    /// this job. When set, the engine reloads it and skips `completed_files` rather than
+    /// reprocessing the whole input from scratch.
+    #[pyo3(get)]
+    pub resume_from: Option<String>,
+    /// Region/endpoint/credential overrides for when `source_path` or `output_path` is an
+    /// `s3://bucket/key` URI (see [`is_object_store_uri`]), so a caller can target an
+    /// S3-compatible service (e.g. a self-hosted MinIO) without relying on the AWS SDK's
+    /// default credential chain. Ignored for local filesystem paths.
+    ///
+    /// Needs the `object-storage` feature (and its `aws-sdk-s3` dependency) added to
+    /// Cargo.toml before the engine actually reads or writes to `s3://` URIs.
+    #[pyo3(get)]
+    pub storage_config: Option<HashMap<String, String>>,
 }
 
 #[pymethods]
@@ -222,38 +578,137 @@ impl JobInput {
     /// * `job_id` - Unique identifier for the job.
     /// * `source_path` - Path to the source data.
     /// * `file_patterns` - List of file patterns to include.
+    /// * `tenant` - The owner this job is reported against.
     /// * `processing_type` - The type of processing to perform.
     /// * `output_path` - Optional path for output.
     /// * `max_concurrency` - Optional maximum concurrency limit.
+    /// * `resume_from` - Optional path to a `JobCheckpoint` to resume from.
+    /// * `storage_config` - Optional region/endpoint/credential overrides for `s3://` paths.
+    ///
+    /// # Errors
+    /// Returns `PravahParameterError` if `processing_type` is a `Pipeline` containing a nested
+    /// `Pipeline` step — see [`reject_nested_pipeline`].
     #[new]
-    #[pyo3(signature = (job_id, source_path, file_patterns, processing_type, output_path = None, max_concurrency = None))]
+    #[pyo3(signature = (job_id, source_path, file_patterns, tenant, processing_type, output_path = None, max_concurrency = None, resume_from = None, storage_config = None))]
     fn new(
         job_id: String,
         source_path: String,
         file_patterns: Vec<String>,
+        tenant: String,
         processing_type: ProcessingType,
         output_path: Option<String>,
         max_concurrency: Option<usize>,
-    ) -> Self {
-        JobInput {
+        resume_from: Option<String>,
+        storage_config: Option<HashMap<String, String>>,
+    ) -> PyResult<Self> {
+        if let ProcessingType::Pipeline { steps } = &processing_type {
+            reject_nested_pipeline(steps)?;
+        }
+
+        Ok(JobInput {
             job_id,
             source_path,
             output_path,
             file_patterns,
+            tenant,
             processing_type,
             max_concurrency,
-        }
+            resume_from,
+            storage_config,
+        })
     }
 
     /// Provides a detailed string representation for Python's repr().
     fn __repr__(&self) -> String {
         format!(
-            "JobInput(job_id='{}', source_path='{}', file_patterns={:?}, processing_type={:?}, max_concurrency={:?})",
-            self.job_id, self.source_path, self.file_patterns, self.processing_type, self.max_concurrency
+            "JobInput(job_id='{}', source_path='{}', tenant='{}', file_patterns={:?}, processing_type={:?}, max_concurrency={:?})",
+            self.job_id, self.source_path, self.tenant, self.file_patterns, self.processing_type, self.max_concurrency
         )
     }
 }
 
+/// A set of related `JobInput`s submitted together, so a Python orchestrator can fan out many
+/// jobs as one unit instead of calling the engine once per job. `id` is engine-assigned (unlike
+/// `JobInput.job_id`, which is caller-supplied) so two batches never collide even if a caller
+/// reuses job IDs across submissions.
+///
+/// Needs `uuid` added to Cargo.toml for [`JobBatch::new`]'s `id` generation.
+#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobBatch {
+    /// Engine-assigned unique identifier for this batch.
+    #[pyo3(get)]
+    pub id: String,
+    /// The jobs making up this batch.
+    #[pyo3(get)]
+    pub jobs: Vec<JobInput>,
+}
+
+#[pymethods]
+impl JobBatch {
+    /// Creates a new `JobBatch` from an ordered list of jobs, assigning it a fresh
+    /// engine-generated `id`.
+    #[new]
+    #[pyo3(signature = (jobs))]
+    fn new(jobs: Vec<JobInput>) -> Self {
+        JobBatch { id: format!("batch-{}", uuid::Uuid::new_v4()), jobs }
+    }
+
+    /// The distinct tenants represented in this batch, in first-seen order, so a caller can
+    /// report a combined type summary grouped by owner without re-deriving it from `jobs`.
+    fn tenants(&self) -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        self.jobs
+            .iter()
+            .filter(|job| seen.insert(job.tenant.clone()))
+            .map(|job| job.tenant.clone())
+            .collect()
+    }
+
+    /// Provides a detailed string representation for Python's repr().
+    fn __repr__(&self) -> String {
+        format!("JobBatch(id='{}', jobs={})", self.id, self.jobs.len())
+    }
+}
+
+/// A serializable snapshot of an in-progress `process_data` job, letting a crashed or
+/// deliberately paused job resume without reprocessing files it already finished.
+///
+/// Not exposed to Python directly: a caller points `JobInput.resume_from` at wherever the
+/// engine wrote one of these, and the engine reloads it via [`JobCheckpoint::load`].
+///
+/// Needs `serde_json` added to Cargo.toml for [`JobCheckpoint::save`]/[`JobCheckpoint::load`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobCheckpoint {
+    /// The job this checkpoint belongs to. Matches `job_input.job_id`.
+    pub job_id: String,
+    /// Paths (relative to `job_input.source_path`) already processed in a prior run; a resume
+    /// skips these.
+    pub completed_files: Vec<String>,
+    /// Paths discovered but not yet processed when the checkpoint was written. The checkpoints
+    /// written by `lib::submit_job` and `lib::submit_batch` leave this empty.
+    pub pending_files: Vec<String>,
+    /// The `JobInput` this checkpoint belongs to, so a resume doesn't need the caller to
+    /// reconstruct matching parameters from scratch.
+    pub job_input: JobInput,
+}
+
+impl JobCheckpoint {
+    /// Writes this checkpoint to `path` as JSON.
+    pub fn save(&self, path: &std::path::Path) -> Result<(), PravahError> {
+        let bytes = serde_json::to_vec_pretty(self)
+            .map_err(|e| PravahError::Internal(format!("Failed to serialize checkpoint: {}", e)))?;
+        std::fs::write(path, bytes).map_err(PravahError::Io)
+    }
+
+    /// Reads a checkpoint previously written by [`JobCheckpoint::save`].
+    pub fn load(path: &std::path::Path) -> Result<Self, PravahError> {
+        let bytes = std::fs::read(path).map_err(PravahError::Io)?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| PravahError::Internal(format!("Failed to deserialize checkpoint at {:?}: {}", path, e)))
+    }
+}
+
 /// Represents internal metadata about a file, typically gathered during directory traversal.
 /// This struct is primarily for internal Rust engine use and not directly exposed to Python
 /// via PyO3, but it derives `Serialize` and `Deserialize` for potential internal state
@@ -284,6 +739,11 @@ pub struct ProcessingResult {
     /// The status of the file's processing (e.g., COMPLETED, FAILED).
     #[pyo3(get)]
     pub status: JobStatus,
+    /// The owner this result is reported against, copied from `JobInput.tenant`, so a caller
+    /// fanning out a `JobBatch` across several tenants can filter and aggregate metrics per
+    /// owner without re-joining results back against their originating jobs.
+    #[pyo3(get)]
+    pub tenant: String,
     /// An optional message providing more details about the processing outcome (e.g., error message).
     #[pyo3(get)]
     pub message: Option<String>,
@@ -299,6 +759,27 @@ pub struct ProcessingResult {
     /// A flexible dictionary for any custom metrics or key-value data specific to the processing type.
     #[pyo3(get)]
     pub custom_metrics: Option<HashMap<String, String>>,
+    /// The total number of files that completed processing successfully, aggregated across
+    /// the whole `process_data` job (rather than this one file). Populated once the job
+    /// finishes or is cancelled; `0` otherwise.
+    #[pyo3(get)]
+    pub total_files_processed: u64,
+    /// The total number of files that failed to process, aggregated across the whole job.
+    #[pyo3(get)]
+    pub errors_encountered: u64,
+    /// Whether the job was stopped early by cancellation (via `JobParameters.cancellation_token`)
+    /// rather than running to completion.
+    #[pyo3(get)]
+    pub cancelled: bool,
+    /// The number of discovered files that were never spawned because cancellation fired
+    /// before traversal reached them.
+    #[pyo3(get)]
+    pub skipped_files: u64,
+    /// Recoverable issues encountered while processing this file (e.g. a skipped malformed
+    /// row) that were handled in place rather than aborting it. These do not flip `status` to
+    /// `FAILED` — a file with warnings still reports `COMPLETED`.
+    #[pyo3(get)]
+    pub warnings: Vec<String>,
 }
 
 #[pymethods]
@@ -309,41 +790,107 @@ impl ProcessingResult {
     /// * `job_id` - The ID of the job this result belongs to.
     /// * `file_path` - The path of the file that was processed.
     /// * `status` - The processing status for this file.
+    /// * `tenant` - The owner this result is reported against.
     /// * `message` - Optional message.
     /// * `output_path` - Optional path to the output file.
     /// * `processing_time_ms` - Time taken for processing in milliseconds.
     /// * `bytes_processed` - Total bytes processed.
     /// * `custom_metrics` - Optional dictionary of custom metrics.
+    /// * `total_files_processed` - Running total of successfully processed files for the job.
+    /// * `errors_encountered` - Running total of failed files for the job.
+    /// * `cancelled` - Whether the job was stopped early by cancellation.
+    /// * `skipped_files` - Discovered files never spawned because of cancellation.
+    /// * `warnings` - Recoverable issues handled in place; does not imply `status = FAILED`.
     #[new]
-    #[pyo3(signature = (job_id, file_path, status, message = None, output_path = None, processing_time_ms = 0, bytes_processed = 0, custom_metrics = None))]
+    #[pyo3(signature = (job_id, file_path, status, tenant, message = None, output_path = None, processing_time_ms = 0, bytes_processed = 0, custom_metrics = None, total_files_processed = 0, errors_encountered = 0, cancelled = false, skipped_files = 0, warnings = Vec::new()))]
     fn new(
         job_id: String,
         file_path: String,
         status: JobStatus,
+        tenant: String,
         message: Option<String>,
         output_path: Option<String>,
         processing_time_ms: u64,
         bytes_processed: u64,
         custom_metrics: Option<HashMap<String, String>>,
+        total_files_processed: u64,
+        errors_encountered: u64,
+        cancelled: bool,
+        skipped_files: u64,
+        warnings: Vec<String>,
     ) -> Self {
         ProcessingResult {
             job_id,
             file_path,
             status,
+            tenant,
             message,
             output_path,
             processing_time_ms,
             bytes_processed,
             custom_metrics,
+            total_files_processed,
+            errors_encountered,
+            cancelled,
+            skipped_files,
+            warnings,
         }
     }
 
     /// Provides a detailed string representation for Python's repr().
     fn __repr__(&self) -> String {
         format!(
-            "ProcessingResult(job_id='{}', file_path='{}', status={:?}, time={}ms, bytes={})",
-            self.job_id, self.file_path, self.status, self.processing_time_ms, self.bytes_processed
+            "ProcessingResult(job_id='{}', file_path='{}', status={:?}, tenant='{}', time={}ms, bytes={}, cancelled={})",
+            self.job_id, self.file_path, self.status, self.tenant, self.processing_time_ms, self.bytes_processed, self.cancelled
         )
     }
 }
-```
\ No newline at end of file
+
+/// A single periodic progress event for one in-flight file, emitted during processing rather
+/// than only once at the end (unlike [`ProcessingResult`], which is terminal). Lets a Python
+/// caller render a live progress bar keyed by `file_path` instead of waiting for completion.
+#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressUpdate {
+    /// The job this update belongs to.
+    #[pyo3(get)]
+    pub job_id: String,
+    /// The file currently being processed.
+    #[pyo3(get)]
+    pub file_path: String,
+    /// Bytes processed so far for this file.
+    #[pyo3(get)]
+    pub bytes_done: u64,
+    /// The file's total size in bytes, if known.
+    #[pyo3(get)]
+    pub bytes_total: u64,
+    /// `bytes_done / bytes_total` as a percentage, already computed so Python callers don't
+    /// need to guard against `bytes_total == 0` themselves.
+    #[pyo3(get)]
+    pub percent: f32,
+}
+
+#[pymethods]
+impl ProgressUpdate {
+    /// Creates a new `ProgressUpdate` instance.
+    ///
+    /// # Arguments
+    /// * `job_id` - The ID of the job this update belongs to.
+    /// * `file_path` - The file currently being processed.
+    /// * `bytes_done` - Bytes processed so far for this file.
+    /// * `bytes_total` - The file's total size in bytes, if known.
+    #[new]
+    #[pyo3(signature = (job_id, file_path, bytes_done, bytes_total))]
+    fn new(job_id: String, file_path: String, bytes_done: u64, bytes_total: u64) -> Self {
+        let percent = if bytes_total > 0 { bytes_done as f32 / bytes_total as f32 * 100.0 } else { 0.0 };
+        ProgressUpdate { job_id, file_path, bytes_done, bytes_total, percent }
+    }
+
+    /// Provides a detailed string representation for Python's repr().
+    fn __repr__(&self) -> String {
+        format!(
+            "ProgressUpdate(job_id='{}', file_path='{}', bytes_done={}, bytes_total={}, percent={:.1})",
+            self.job_id, self.file_path, self.bytes_done, self.bytes_total, self.percent
+        )
+    }
+}
\ No newline at end of file
@@ -0,0 +1,261 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use tokio_util::sync::CancellationToken;
+
+use crate::error::PravahError;
+use crate::models::ProgressUpdate;
+
+/// Callbacks invoked as `process_data` discovers and processes files.
+///
+/// Implementations must be `Send + Sync` since callbacks fire from concurrently
+/// spawned Tokio tasks. The default methods are no-ops so embedders only need to
+/// override the events they care about.
+pub trait ProgressReporter: Send + Sync {
+    /// Called once a file has been discovered by directory traversal and queued for processing.
+    fn on_discovered(&self, _path: &Path) {}
+
+    /// Called when a file finishes processing successfully.
+    fn on_completed(&self, _path: &Path, _bytes_processed: u64) {}
+
+    /// Called when a file fails to process.
+    fn on_error(&self, _path: &Path, _err: &PravahError) {}
+
+    /// Called once traversal has finished discovering files, with the total count found so
+    /// far. Reporters that render a determinate bar should switch from a spinner to a ratio
+    /// bar at this point.
+    fn on_discovery_complete(&self, _total_discovered: u64) {}
+}
+
+/// A `ProgressReporter` that renders a live terminal progress bar via `termprogress`.
+///
+/// Until [`ProgressReporter::on_discovery_complete`] fires, the bar renders as an
+/// indeterminate spinner (since traversal and processing are interleaved and the total
+/// file count isn't known yet); afterwards it switches to a ratio bar with counts and
+/// throughput.
+pub struct TerminalProgressReporter {
+    bar: std::sync::Mutex<termprogress::Progress>,
+    discovered: AtomicU64,
+    completed: AtomicU64,
+    errored: AtomicU64,
+}
+
+impl TerminalProgressReporter {
+    pub fn new() -> Self {
+        TerminalProgressReporter {
+            bar: std::sync::Mutex::new(termprogress::Progress::spinner("Scanning...")),
+            discovered: AtomicU64::new(0),
+            completed: AtomicU64::new(0),
+            errored: AtomicU64::new(0),
+        }
+    }
+
+    fn render(&self) {
+        let done = self.completed.load(Ordering::Relaxed) + self.errored.load(Ordering::Relaxed);
+        let total = self.discovered.load(Ordering::Relaxed);
+        let mut bar = self.bar.lock().expect("progress bar mutex poisoned");
+        bar.set_title(&format!(
+            "{}/{} files ({} errors)",
+            done,
+            total,
+            self.errored.load(Ordering::Relaxed)
+        ));
+        if total > 0 {
+            bar.set_progress(done as f64 / total as f64);
+        }
+    }
+}
+
+impl Default for TerminalProgressReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressReporter for TerminalProgressReporter {
+    fn on_discovered(&self, _path: &Path) {
+        self.discovered.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_completed(&self, _path: &Path, _bytes_processed: u64) {
+        self.completed.fetch_add(1, Ordering::Relaxed);
+        self.render();
+    }
+
+    fn on_error(&self, path: &Path, err: &PravahError) {
+        self.errored.fetch_add(1, Ordering::Relaxed);
+        log::warn!("Progress: error processing {:?}: {}", path, err);
+        self.render();
+    }
+
+    fn on_discovery_complete(&self, total_discovered: u64) {
+        self.discovered.store(total_discovered, Ordering::Relaxed);
+        let mut bar = self.bar.lock().expect("progress bar mutex poisoned");
+        bar.to_ratio_bar();
+        drop(bar);
+        self.render();
+    }
+}
+
+/// A `ProgressReporter` that forwards progress to a Python callback supplied as
+/// `progress_callback` on `process_files`/`process_files_async`, so a caller (a Celery task
+/// updating job state, a tqdm bar) can observe a long-running job without polling.
+///
+/// The GIL is only held for the duration of each callback invocation — the rest of
+/// `process_data` keeps running with it released. If the callback returns `False` or raises,
+/// that's treated as a cooperative cancel signal: this reporter cancels `cancellation_token`,
+/// which makes `process_data` stop spawning new file tasks, drain in-flight ones, and return a
+/// `ProcessingResult` with `cancelled = true` rather than panicking or raising.
+pub struct PyCallbackProgressReporter {
+    job_id: String,
+    callback: Py<PyAny>,
+    cancellation_token: Arc<CancellationToken>,
+    files_processed: AtomicU64,
+    bytes_processed: AtomicU64,
+    total_discovered: AtomicU64,
+    /// The most recently completed file's path and bytes processed, used to build the
+    /// `ProgressUpdate` embedded in each `report()` payload.
+    last_completed_file: Mutex<Option<(String, u64)>>,
+}
+
+impl PyCallbackProgressReporter {
+    pub fn new(job_id: String, callback: Py<PyAny>, cancellation_token: Arc<CancellationToken>) -> Self {
+        PyCallbackProgressReporter {
+            job_id,
+            callback,
+            cancellation_token,
+            files_processed: AtomicU64::new(0),
+            bytes_processed: AtomicU64::new(0),
+            total_discovered: AtomicU64::new(0),
+            last_completed_file: Mutex::new(None),
+        }
+    }
+
+    /// Invokes the Python callback with `{files_processed, bytes_processed, percent, last_file}`,
+    /// where `last_file` (a [`ProgressUpdate`] for the most recently completed file, or `None`
+    /// before any file has completed) lets a caller render per-file progress rather than only a
+    /// job-wide count. Cancels `cancellation_token` if the callback returns `False` or raises.
+    fn report(&self) {
+        let files_processed = self.files_processed.load(Ordering::Relaxed);
+        let bytes_processed = self.bytes_processed.load(Ordering::Relaxed);
+        let total_discovered = self.total_discovered.load(Ordering::Relaxed);
+        let percent = if total_discovered > 0 {
+            files_processed as f64 / total_discovered as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        // `ProgressUpdate::new` is a PyO3 `#[new]` constructor, only reachable from Python; built
+        // directly here instead, since every field is `pub` for exactly this reason.
+        let last_file = self
+            .last_completed_file
+            .lock()
+            .expect("last_completed_file mutex poisoned")
+            .clone()
+            .map(|(file_path, file_bytes)| ProgressUpdate {
+                job_id: self.job_id.clone(),
+                file_path,
+                bytes_done: file_bytes,
+                bytes_total: file_bytes,
+                percent: if file_bytes > 0 { 100.0 } else { 0.0 },
+            });
+
+        let should_cancel = Python::with_gil(|py| {
+            let progress = PyDict::new(py);
+            if progress.set_item("files_processed", files_processed).is_err()
+                || progress.set_item("bytes_processed", bytes_processed).is_err()
+                || progress.set_item("percent", percent).is_err()
+                || progress.set_item("last_file", last_file).is_err()
+            {
+                return false;
+            }
+
+            match self.callback.call1(py, (progress,)) {
+                Ok(result) => matches!(result.extract::<bool>(py), Ok(false)),
+                Err(err) => {
+                    log::warn!("progress_callback raised, treating it as a cancel signal: {}", err);
+                    true
+                }
+            }
+        });
+
+        if should_cancel {
+            self.cancellation_token.cancel();
+        }
+    }
+}
+
+impl ProgressReporter for PyCallbackProgressReporter {
+    fn on_discovered(&self, _path: &Path) {
+        self.total_discovered.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_completed(&self, path: &Path, bytes_processed: u64) {
+        self.files_processed.fetch_add(1, Ordering::Relaxed);
+        self.bytes_processed.fetch_add(bytes_processed, Ordering::Relaxed);
+        *self.last_completed_file.lock().expect("last_completed_file mutex poisoned") =
+            Some((path.to_string_lossy().into_owned(), bytes_processed));
+        self.report();
+    }
+
+    fn on_error(&self, path: &Path, err: &PravahError) {
+        self.files_processed.fetch_add(1, Ordering::Relaxed);
+        log::warn!("Progress: error processing {:?}: {}", path, err);
+        self.report();
+    }
+
+    fn on_discovery_complete(&self, total_discovered: u64) {
+        self.total_discovered.store(total_discovered, Ordering::Relaxed);
+        self.report();
+    }
+}
+
+/// A `ProgressReporter` that wraps another reporter, forwarding every call unchanged while
+/// additionally recording each completed file's path (relative to `source_path`) so
+/// `lib::submit_job` can fold them into a [`crate::models::JobCheckpoint`] once the job
+/// finishes. Reuses the existing `ProgressReporter` extension point rather than threading a new
+/// out-parameter through `engine::process_data`.
+pub struct CheckpointingProgressReporter {
+    inner: Arc<dyn ProgressReporter>,
+    source_path: std::path::PathBuf,
+    completed: Mutex<Vec<String>>,
+}
+
+impl CheckpointingProgressReporter {
+    pub fn new(inner: Arc<dyn ProgressReporter>, source_path: std::path::PathBuf) -> Self {
+        CheckpointingProgressReporter {
+            inner,
+            source_path,
+            completed: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The relative paths (relative to `source_path`) of every file completed so far, for
+    /// writing into a [`crate::models::JobCheckpoint`].
+    pub fn completed_relative_paths(&self) -> Vec<String> {
+        self.completed.lock().expect("completed mutex poisoned").clone()
+    }
+}
+
+impl ProgressReporter for CheckpointingProgressReporter {
+    fn on_discovered(&self, path: &Path) {
+        self.inner.on_discovered(path);
+    }
+
+    fn on_completed(&self, path: &Path, bytes_processed: u64) {
+        let relative_path = path.strip_prefix(&self.source_path).unwrap_or(path).to_string_lossy().into_owned();
+        self.completed.lock().expect("completed mutex poisoned").push(relative_path);
+        self.inner.on_completed(path, bytes_processed);
+    }
+
+    fn on_error(&self, path: &Path, err: &PravahError) {
+        self.inner.on_error(path, err);
+    }
+
+    fn on_discovery_complete(&self, total_discovered: u64) {
+        self.inner.on_discovery_complete(total_discovered);
+    }
+}
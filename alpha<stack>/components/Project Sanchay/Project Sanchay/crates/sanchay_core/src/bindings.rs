@@ -4,9 +4,44 @@ use pyo3::types::{PyDict, PyList};
 use pyo3::wrap_pyfunction;
 
 // Import core logic from other modules within sanchay_core
+use crate::database::DatabaseManager;
 use crate::error::SanchayCoreError;
 use crate::file_processor::ChecksumAlgorithm;
+use crate::incremental;
 use crate::walker;
+use crate::walker::{DifferenceMatcher, GlobMatcher, Matcher};
+use std::path::Path;
+
+/// Builds a composed `Matcher` from optional include/exclude glob pattern lists, for use by the
+/// Python-facing scan functions below. Returns `None` when neither list is given, so callers that
+/// don't pass patterns see no change in behavior.
+fn build_matcher(
+    include_patterns: Option<Vec<String>>,
+    exclude_patterns: Option<Vec<String>>,
+) -> PyResult<Option<Box<dyn Matcher>>> {
+    let included: Option<Box<dyn Matcher>> = match include_patterns {
+        Some(patterns) => Some(Box::new(GlobMatcher::new(&patterns)?)),
+        None => None,
+    };
+    let excluded: Option<Box<dyn Matcher>> = match exclude_patterns {
+        Some(patterns) => Some(Box::new(GlobMatcher::new(&patterns)?)),
+        None => None,
+    };
+
+    let matcher = match (included, excluded) {
+        (Some(included), Some(excluded)) => Some(Box::new(DifferenceMatcher::new(included, excluded)) as Box<dyn Matcher>),
+        (Some(included), None) => Some(included),
+        (None, Some(_)) => {
+            return Err(SanchayCoreError::ProcessingError(
+                "exclude_patterns requires include_patterns to also be set".to_string(),
+            )
+            .into())
+        }
+        (None, None) => None,
+    };
+
+    Ok(matcher)
+}
 
 /// A simple greeting function for testing Python-Rust integration.
 #[pyfunction]
@@ -21,6 +56,9 @@ fn greet() -> PyResult<String> {
 ///     include_checksum (bool, optional): Whether to calculate checksums for files. Defaults to False.
 ///     checksum_algorithm (str, optional): The algorithm to use for checksums (e.g., "sha256", "md5").
 ///                                         Defaults to "sha256".
+///     include_patterns (list[str], optional): Glob patterns a file's path must match to be included.
+///     exclude_patterns (list[str], optional): Glob patterns a file's path must not match. Requires
+///                                             include_patterns to also be set.
 ///
 /// Returns:
 ///     list[dict]: A list of dictionaries, each representing file metadata.
@@ -29,21 +67,29 @@ fn greet() -> PyResult<String> {
 /// Raises:
 ///     ValueError: If an invalid checksum algorithm is provided or if a Rust core error occurs.
 ///     OSError: If an I/O related error occurs during directory traversal or file access.
+// NOTE: `walker::scan_directory_for_metadata` (unlike `walk_directory_parallel`) returns a bare
+// `Vec<FileMetadata>` with no per-path traversal warnings, so this returns no warning list.
+// `walk_directory_classified` below returns `{path, reason}` dicts for rejected entry types,
+// not traversal warnings.
 #[pyfunction]
+#[pyo3(signature = (directory_path, include_checksum=None, checksum_algorithm=None, include_patterns=None, exclude_patterns=None))]
 fn get_file_metadata(
     py: Python,
     directory_path: &str,
     include_checksum: Option<bool>,
     checksum_algorithm: Option<&str>,
+    include_patterns: Option<Vec<String>>,
+    exclude_patterns: Option<Vec<String>>,
 ) -> PyResult<Py<PyList>> {
     let include_checksum = include_checksum.unwrap_or(false);
     let algorithm = checksum_algorithm
         .map(|s| ChecksumAlgorithm::from_str(s))
         .transpose()?; // Automatically converts SanchayCoreError to PyErr
     let algorithm = algorithm.unwrap_or(ChecksumAlgorithm::SHA256); // Default
+    let matcher = build_matcher(include_patterns, exclude_patterns)?;
 
     let result = py.allow_threads(move || {
-        walker::scan_directory_for_metadata(directory_path, include_checksum, algorithm)
+        walker::scan_directory_for_metadata(directory_path, include_checksum, algorithm, matcher.as_deref())
     });
 
     match result {
@@ -79,6 +125,9 @@ fn get_file_metadata(
 ///     directory_path (str): The path to the directory to scan.
 ///     checksum_algorithm (str, optional): The algorithm to use for checksums (e.g., "sha256", "md5").
 ///                                         Defaults to "sha256".
+///     include_patterns (list[str], optional): Glob patterns a file's path must match to be included.
+///     exclude_patterns (list[str], optional): Glob patterns a file's path must not match. Requires
+///                                             include_patterns to also be set.
 ///
 /// Returns:
 ///     list[list[str]]: A list of lists, where each inner list contains paths of duplicate files.
@@ -88,18 +137,22 @@ fn get_file_metadata(
 ///     ValueError: If an invalid checksum algorithm is provided or if a Rust core error occurs.
 ///     OSError: If an I/O related error occurs during directory traversal or file access.
 #[pyfunction]
+#[pyo3(signature = (directory_path, checksum_algorithm=None, include_patterns=None, exclude_patterns=None))]
 fn find_duplicates(
     py: Python,
     directory_path: &str,
     checksum_algorithm: Option<&str>,
+    include_patterns: Option<Vec<String>>,
+    exclude_patterns: Option<Vec<String>>,
 ) -> PyResult<Py<PyList>> {
     let algorithm = checksum_algorithm
         .map(|s| ChecksumAlgorithm::from_str(s))
         .transpose()?;
     let algorithm = algorithm.unwrap_or(ChecksumAlgorithm::SHA256);
+    let matcher = build_matcher(include_patterns, exclude_patterns)?;
 
     let result = py.allow_threads(move || {
-        walker::find_duplicate_files_in_directory(directory_path, algorithm)
+        walker::find_duplicate_files_in_directory(directory_path, algorithm, matcher.as_deref())
     });
 
     match result {
@@ -118,6 +171,136 @@ fn find_duplicates(
     }
 }
 
+/// Rescans `directory_path`, re-hashing only files whose size or modification time changed since
+/// the last scan recorded in `db_path`, and reports what changed.
+///
+/// Args:
+///     directory_path (str): The directory to rescan.
+///     db_path (str): Path to the SQLite database holding metadata from previous scans.
+///
+/// Returns:
+///     dict: A dict with keys 'added', 'modified', 'deleted', 'unchanged', each a list of paths
+///     (as strings) of files that fall into that category since the database's last record of
+///     this directory.
+///
+/// Raises:
+///     OSError: If an I/O error occurs while walking or stat-ing files.
+///     RuntimeError: If the database cannot be opened or a query fails.
+#[pyfunction]
+fn incremental_scan(py: Python, directory_path: &str, db_path: &str) -> PyResult<Py<PyDict>> {
+    let result = py.allow_threads(move || {
+        let manager = DatabaseManager::new(Path::new(db_path))?;
+        incremental::incremental_scan(&manager, Path::new(directory_path))
+    });
+
+    match result {
+        Ok(change_set) => {
+            let dict = PyDict::new(py);
+            dict.set_item("added", paths_to_strings(&change_set.added))?;
+            dict.set_item("modified", paths_to_strings(&change_set.modified))?;
+            dict.set_item("deleted", paths_to_strings(&change_set.deleted))?;
+            dict.set_item("unchanged", paths_to_strings(&change_set.unchanged))?;
+            Ok(dict.into())
+        }
+        Err(e) => Err(e.into()), // Convert SanchayCoreError to PyErr
+    }
+}
+
+fn paths_to_strings(paths: &[std::path::PathBuf]) -> Vec<String> {
+    paths.iter().map(|p| p.to_string_lossy().into_owned()).collect()
+}
+
+/// Walks `directory_path` like `get_file_metadata`'s traversal, but instead of silently dropping
+/// symlinks, FIFOs, sockets, device files, and directories, reports each one alongside the reason
+/// it was rejected so a caller can warn about special files it refused to process.
+///
+/// Args:
+///     directory_path (str): The path to the directory to scan.
+///
+/// Returns:
+///     tuple[list[str], list[dict]]: The discovered regular file paths, and a list of
+///     `{path, reason}` dicts for every rejected path (`reason` is one of `character_device`,
+///     `block_device`, `fifo`, `socket`, `directory`, `symlink`, `unknown`).
+///
+/// Raises:
+///     OSError: If `directory_path` does not exist.
+#[pyfunction]
+fn walk_directory_classified(py: Python, directory_path: &str) -> PyResult<(Py<PyList>, Py<PyList>)> {
+    let result = py.allow_threads(move || walker::walk_directory_classified(Path::new(directory_path)));
+
+    match result {
+        Ok((files, rejected)) => {
+            let files_list = PyList::new(py, paths_to_strings(&files));
+            let rejected_list = PyList::empty(py);
+            for (path, bad_type) in rejected {
+                let dict = PyDict::new(py);
+                dict.set_item("path", path.to_string_lossy().into_owned())?;
+                dict.set_item("reason", bad_type.as_str())?;
+                rejected_list.append(dict)?;
+            }
+            Ok((files_list.into(), rejected_list.into()))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Rescans `directory_path`, consulting a directory-mtime cache persisted at `cache_path` to
+/// skip re-hashing files that haven't changed since the cache was last written. Unlike
+/// `incremental_scan`, which needs a `db_path` with a prior full scan's rows, this works from a
+/// single sidecar cache file. Every directory is still listed and every file stat-ed; unchanged
+/// directories are not skipped, only the re-hashing of unchanged files.
+///
+/// Args:
+///     directory_path (str): The directory to rescan.
+///     cache_path (str): Path to the JSON sidecar cache of directory mtimes and child entries.
+///     checksum_algorithm (str, optional): Validated, but every checksum is Blake3 whichever
+///                                         algorithm is passed. Defaults to "sha256".
+///
+/// Returns:
+///     list[dict]: A list of dictionaries, each representing file metadata, in the same shape as
+///     `get_file_metadata`.
+///
+/// Raises:
+///     RuntimeError: If an invalid checksum algorithm is provided.
+///     ValueError: If the cache file cannot be parsed.
+///     OSError: If an I/O related error occurs during directory traversal, file access, or
+///     reading/writing the cache file.
+#[pyfunction]
+#[pyo3(signature = (directory_path, cache_path, checksum_algorithm=None))]
+fn scan_directory_incremental(
+    py: Python,
+    directory_path: &str,
+    cache_path: &str,
+    checksum_algorithm: Option<&str>,
+) -> PyResult<Py<PyList>> {
+    let algorithm = checksum_algorithm
+        .map(|s| ChecksumAlgorithm::from_str(s))
+        .transpose()?;
+    let algorithm = algorithm.unwrap_or(ChecksumAlgorithm::SHA256);
+
+    let result = py.allow_threads(move || {
+        walker::scan_directory_incremental(Path::new(directory_path), Path::new(cache_path), algorithm)
+    });
+
+    match result {
+        Ok(metadata_vec) => {
+            let py_list = PyList::empty(py);
+            for meta in metadata_vec {
+                let dict = PyDict::new(py);
+                dict.set_item("path", meta.path.to_string_lossy().into_owned())?;
+                dict.set_item("size", meta.size)?;
+                dict.set_item("modified_at", meta.modified_at)?;
+                dict.set_item("created_at", meta.created_at)?;
+                dict.set_item("checksum", meta.checksum)?;
+                dict.set_item("mime_type", meta.mime_type)?;
+                py_list.append(dict)?;
+            }
+            Ok(py_list.into())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// A Python module implemented in Rust, providing high-performance file processing capabilities.
 ///
 /// This module exposes functions to scan directories, collect file metadata, and find duplicate files
@@ -127,6 +310,9 @@ fn sanchay_core(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(greet, m)?)?;
     m.add_function(wrap_pyfunction!(get_file_metadata, m)?)?;
     m.add_function(wrap_pyfunction!(find_duplicates, m)?)?;
+    m.add_function(wrap_pyfunction!(walk_directory_classified, m)?)?;
+    m.add_function(wrap_pyfunction!(incremental_scan, m)?)?;
+    m.add_function(wrap_pyfunction!(scan_directory_incremental, m)?)?;
 
     // Optionally, add custom Python exceptions if a more granular error handling
     // is desired on the Python side than PyValueError/PyIOError.
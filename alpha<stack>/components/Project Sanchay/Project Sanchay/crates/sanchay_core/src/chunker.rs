@@ -0,0 +1,133 @@
+//! FastCDC-style content-defined chunking, used by `database` to detect files that share large
+//! byte ranges even when they differ slightly (sub-file, as opposed to whole-file, dedup).
+
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+
+use crate::error::{Error, Result};
+
+/// No chunk boundary is cut before a chunk reaches this size; only the last chunk of the input may be shorter.
+pub const MIN_CHUNK_SIZE: usize = 2 * 1024;
+/// The chunker is tuned so that, on average, chunk boundaries land around this many bytes apart.
+pub const AVG_CHUNK_SIZE: usize = 8 * 1024;
+/// Chunks are force-cut at this size even if no boundary condition is met, bounding worst case.
+pub const MAX_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Normalized chunking: below `AVG_CHUNK_SIZE` we require more zero bits (harder to satisfy),
+/// discouraging premature cuts; at or above it we require fewer zero bits (easier to satisfy),
+/// pulling the boundary back towards the average. This keeps chunk sizes tightly clustered
+/// around `AVG_CHUNK_SIZE` instead of following a wide exponential spread.
+const MASK_SMALL: u64 = 0x0000_d93003530000;
+const MASK_LARGE: u64 = 0x0000_d90003530000;
+
+/// A fixed table of 256 pseudo-random `u64` "gear" values, one per possible input byte. Built
+/// once per process via a deterministic splitmix64 generator (not from an external RNG) so that
+/// chunk boundaries are reproducible across runs and machines.
+static GEAR: Lazy<[u64; 256]> = Lazy::new(|| {
+    let mut table = [0u64; 256];
+    let mut seed: u64 = 0x9E3779B97F4A7C15;
+    for slot in table.iter_mut() {
+        seed = seed.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        *slot = z;
+    }
+    table
+});
+
+/// A single content-defined chunk produced by [`chunk_reader`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// SHA-256 hex digest of the chunk's bytes; used as its content-addressed identifier.
+    pub chunk_id: String,
+    /// Byte offset of this chunk within the file it was read from.
+    pub offset: u64,
+    /// Length of this chunk in bytes.
+    pub len: u64,
+}
+
+/// Splits the bytes read from `reader` into content-defined chunks using FastCDC's gear-hash
+/// rolling window with normalized chunking. Each chunk is hashed with SHA-256 as it's produced
+/// so the whole file never needs to be buffered in memory at once.
+pub fn chunk_reader<R: Read>(mut reader: R) -> Result<Vec<Chunk>> {
+    let mut chunks = Vec::new();
+    let mut offset: u64 = 0;
+    let mut buf = [0u8; 8192];
+    let mut carry: Vec<u8> = Vec::new();
+
+    loop {
+        let read = reader.read(&mut buf).map_err(Error::Io)?;
+        if read == 0 {
+            break;
+        }
+        carry.extend_from_slice(&buf[..read]);
+
+        // Cut as many chunks as we can out of the accumulated carry buffer; anything left
+        // over (shorter than a full chunk) stays in `carry` for the next read.
+        loop {
+            match find_boundary(&carry) {
+                Some(boundary) => {
+                    let chunk_bytes = &carry[..boundary];
+                    chunks.push(Chunk {
+                        chunk_id: sha256_hex(chunk_bytes),
+                        offset,
+                        len: boundary as u64,
+                    });
+                    offset += boundary as u64;
+                    carry.drain(..boundary);
+                }
+                None => break,
+            }
+        }
+    }
+
+    if !carry.is_empty() {
+        chunks.push(Chunk {
+            chunk_id: sha256_hex(&carry),
+            offset,
+            len: carry.len() as u64,
+        });
+    }
+
+    Ok(chunks)
+}
+
+/// Scans `data` for the next chunk boundary using the gear hash, returning the length (from the
+/// start of `data`) of the chunk that should be cut, or `None` if no boundary is found yet and
+/// more data is needed (unless `data` has already hit `MAX_CHUNK_SIZE`, in which case it force-cuts).
+fn find_boundary(data: &[u8]) -> Option<usize> {
+    if data.len() < MIN_CHUNK_SIZE {
+        if data.len() >= MAX_CHUNK_SIZE {
+            return Some(MAX_CHUNK_SIZE);
+        }
+        return None;
+    }
+
+    let mut hash: u64 = 0;
+    for (i, &byte) in data.iter().enumerate() {
+        if i >= MAX_CHUNK_SIZE {
+            return Some(MAX_CHUNK_SIZE);
+        }
+        hash = (hash << 1).wrapping_add(GEAR[byte as usize]);
+
+        if i + 1 < MIN_CHUNK_SIZE {
+            continue;
+        }
+
+        let mask = if i + 1 < AVG_CHUNK_SIZE { MASK_SMALL } else { MASK_LARGE };
+        if hash & mask == 0 {
+            return Some(i + 1);
+        }
+    }
+
+    None
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
@@ -1,6 +1,9 @@
 use rusqlite::{Connection, params, TransactionBehavior, Result as RusqliteResult};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use crate::chunker::Chunk;
 use crate::error::SanchayCoreError; // Assuming error.rs defines SanchayCoreError
+use crate::store::MetadataStore;
 
 /// Represents file metadata to be stored in the database.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -11,44 +14,278 @@ pub struct FileMetadata {
     pub checksum: String, // e.g., SHA256 hash
     pub modified_at: u64, // Unix timestamp (seconds since epoch)
     pub created_at: u64,  // Unix timestamp (seconds since epoch)
+    /// The file's content type, detected from magic numbers in its leading bytes (see
+    /// `file_processor::sniff_mime_type`) rather than trusted from its extension, so
+    /// extension-less or mislabeled files are still classified correctly.
+    pub mime_type: String,
 }
 
+/// A pair of files that share at least one content-defined chunk, with the overlap expressed
+/// both as an absolute byte count and as a fraction of each file's own size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkOverlap {
+    pub path_a: PathBuf,
+    pub path_b: PathBuf,
+    pub shared_bytes: u64,
+    pub overlap_ratio_a: f64,
+    pub overlap_ratio_b: f64,
+}
+
+/// The schema version this binary was built against. Bumped whenever a migration is added to
+/// `MIGRATIONS`; an on-disk database with a newer major version refuses to open rather than risk
+/// misreading a layout this binary doesn't understand (mirroring obnam's `SCHEMA_MAJOR`/`MINOR`
+/// and Mercurial's dirstate-v2-with-v1-fallback approach).
+pub const CURRENT_SCHEMA_MAJOR: u32 = 1;
+pub const CURRENT_SCHEMA_MINOR: u32 = 1;
+
+/// Returns the schema version this binary was built against, as `(major, minor)`.
+pub fn current_schema_version() -> (u32, u32) {
+    (CURRENT_SCHEMA_MAJOR, CURRENT_SCHEMA_MINOR)
+}
+
+/// One migration step, applied inside the same transaction as the version bump that follows it.
+/// Each closure must be idempotent (safe to re-run against a database that already has the
+/// migration applied) so a process that crashes mid-upgrade can simply be restarted.
+struct Migration {
+    minor: u32,
+    apply: fn(&rusqlite::Transaction) -> RusqliteResult<()>,
+}
+
+/// Ordered list of migrations bringing a fresh (empty) or older database up to
+/// `CURRENT_SCHEMA_MAJOR.CURRENT_SCHEMA_MINOR`. All migrations here are within major version 1;
+/// a future breaking layout change would bump `CURRENT_SCHEMA_MAJOR` and add a new migration
+/// table rather than editing these in place.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        minor: 0,
+        apply: |tx| {
+            tx.execute_batch(
+                "CREATE TABLE IF NOT EXISTS files (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    path TEXT NOT NULL UNIQUE,
+                    file_name TEXT NOT NULL,
+                    size INTEGER NOT NULL,
+                    checksum TEXT NOT NULL,
+                    modified_at INTEGER NOT NULL,
+                    created_at INTEGER NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_files_checksum ON files (checksum);
+                CREATE INDEX IF NOT EXISTS idx_files_path ON files (path);
+
+                CREATE TABLE IF NOT EXISTS chunks (
+                    chunk_id TEXT NOT NULL,
+                    file_id INTEGER NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+                    offset INTEGER NOT NULL,
+                    len INTEGER NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_chunks_chunk_id ON chunks (chunk_id);
+                CREATE INDEX IF NOT EXISTS idx_chunks_file_id ON chunks (file_id);
+                ",
+            )
+        },
+    },
+    Migration {
+        minor: 1,
+        apply: |tx| {
+            // SQLite has no `ADD COLUMN IF NOT EXISTS`, so check the column list first; this
+            // keeps the migration idempotent for a process that crashes after adding the column
+            // but before the version bump commits.
+            let mime_column_exists: bool = tx
+                .prepare("SELECT 1 FROM pragma_table_info('files') WHERE name = 'mime_type'")?
+                .exists(params![])?;
+            if !mime_column_exists {
+                tx.execute_batch("ALTER TABLE files ADD COLUMN mime_type TEXT NOT NULL DEFAULT ''")?;
+            }
+            tx.execute_batch(
+                "CREATE INDEX IF NOT EXISTS idx_files_mime_type ON files (mime_type);",
+            )
+        },
+    },
+];
+
 /// Manages interaction with the SQLite database for file metadata.
 pub struct DatabaseManager {
     conn: Connection,
 }
 
 impl DatabaseManager {
-    /// Opens a connection to the SQLite database at the specified path.
-    /// If the database file does not exist, it will be created.
-    /// Also ensures the necessary schema tables are set up.
+    /// Opens a connection to the SQLite database at the specified path, creating it if needed,
+    /// and brings its schema up to `CURRENT_SCHEMA_MAJOR.CURRENT_SCHEMA_MINOR` via `MIGRATIONS`.
     pub fn new(db_path: &Path) -> Result<Self, SanchayCoreError> {
         let conn = Connection::open(db_path).map_err(SanchayCoreError::DbConnection)?;
+        conn.execute_batch("PRAGMA foreign_keys = ON;").map_err(SanchayCoreError::DbConnection)?;
         let manager = DatabaseManager { conn };
-        manager.setup_schema()?;
+        manager.run_migrations()?;
         Ok(manager)
     }
 
-    /// Sets up the required database tables if they do not already exist.
-    /// Creates the 'files' table with necessary columns and indexes.
-    fn setup_schema(&self) -> Result<(), SanchayCoreError> {
+    /// Reads the stored `schema_version` (major 0, minor 0 if the table doesn't exist yet,
+    /// i.e. a brand-new database) and applies every migration with a minor version greater
+    /// than what's on disk, all inside one transaction together with the version bump.
+    /// Refuses to proceed if the on-disk major version is newer than this binary supports.
+    fn run_migrations(&self) -> Result<(), SanchayCoreError> {
         self.conn.execute_batch(
-            "CREATE TABLE IF NOT EXISTS files (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                path TEXT NOT NULL UNIQUE,
-                file_name TEXT NOT NULL,
-                size INTEGER NOT NULL,
-                checksum TEXT NOT NULL,
-                modified_at INTEGER NOT NULL,
-                created_at INTEGER NOT NULL
-            );
-            CREATE INDEX IF NOT EXISTS idx_files_checksum ON files (checksum);
-            CREATE INDEX IF NOT EXISTS idx_files_path ON files (path);
-            ",
+            "CREATE TABLE IF NOT EXISTS schema_version (major INTEGER NOT NULL, minor INTEGER NOT NULL)",
         ).map_err(SanchayCoreError::DbSchema)?;
+
+        let stored: Option<(u32, u32)> = self.conn
+            .query_row("SELECT major, minor FROM schema_version LIMIT 1", params![], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .map(Some)
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                other => Err(SanchayCoreError::DbQuery(other)),
+            })?;
+
+        let (on_disk_major, on_disk_minor) = stored.unwrap_or((0, 0));
+
+        if on_disk_major > CURRENT_SCHEMA_MAJOR {
+            return Err(SanchayCoreError::UnsupportedSchemaVersion {
+                on_disk_major,
+                on_disk_minor,
+                supported_major: CURRENT_SCHEMA_MAJOR,
+                supported_minor: CURRENT_SCHEMA_MINOR,
+            });
+        }
+
+        let pending: Vec<&Migration> = MIGRATIONS.iter()
+            .filter(|m| on_disk_major < CURRENT_SCHEMA_MAJOR || m.minor > on_disk_minor)
+            .collect();
+
+        if !pending.is_empty() {
+            let tx = self.conn.transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(SanchayCoreError::DbTransaction)?;
+            for migration in &pending {
+                (migration.apply)(&tx).map_err(SanchayCoreError::DbSchema)?;
+            }
+            tx.execute("DELETE FROM schema_version", params![])
+                .map_err(SanchayCoreError::DbSchema)?;
+            tx.execute(
+                "INSERT INTO schema_version (major, minor) VALUES (?, ?)",
+                params![CURRENT_SCHEMA_MAJOR, CURRENT_SCHEMA_MINOR],
+            ).map_err(SanchayCoreError::DbSchema)?;
+            tx.commit().map_err(SanchayCoreError::DbTransaction)?;
+        }
+
+        Ok(())
+    }
+
+    /// Looks up a file's internal row id by its path, used to associate chunks with the file
+    /// that produced them.
+    fn get_file_id_by_path(&self, path: &Path) -> Result<Option<i64>, SanchayCoreError> {
+        self.conn
+            .query_row(
+                "SELECT id FROM files WHERE path = ?",
+                params![path.to_string_lossy()],
+                |row| row.get(0),
+            )
+            .map(Some)
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                other => Err(SanchayCoreError::DbQuery(other)),
+            })
+    }
+
+    /// Replaces the stored content-defined chunks for `path` (looked up via `files.path`, which
+    /// must already have a row) with `chunks`. Re-running this for an unchanged file is safe:
+    /// the previous chunk rows for that file are deleted first.
+    pub fn replace_file_chunks(&self, path: &Path, chunks: &[Chunk]) -> Result<(), SanchayCoreError> {
+        let file_id = self
+            .get_file_id_by_path(path)?
+            .ok_or_else(|| SanchayCoreError::ProcessingError(format!("No file row for path {:?}; insert its metadata first", path)))?;
+
+        let tx = self.conn.transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(SanchayCoreError::DbTransaction)?;
+
+        tx.execute("DELETE FROM chunks WHERE file_id = ?", params![file_id])
+            .map_err(SanchayCoreError::DbStatement)?;
+
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT INTO chunks (chunk_id, file_id, offset, len) VALUES (?, ?, ?, ?)",
+            ).map_err(SanchayCoreError::DbStatement)?;
+
+            for chunk in chunks {
+                stmt.execute(params![chunk.chunk_id, file_id, chunk.offset, chunk.len])
+                    .map_err(SanchayCoreError::DbInsert)?;
+            }
+        }
+
+        tx.commit().map_err(SanchayCoreError::DbTransaction)?;
         Ok(())
     }
 
+    /// Groups files that share at least one content-defined chunk, reporting how many bytes of
+    /// overlap each pair has relative to each file's own size. This detects partial overlap
+    /// (e.g. near-duplicate files that differ by a few edits) that whole-file checksum
+    /// comparison (`get_duplicate_files`) misses entirely.
+    pub fn get_files_sharing_chunks(&self) -> Result<Vec<ChunkOverlap>, SanchayCoreError> {
+        let mut shared_chunk_ids_stmt = self.conn.prepare(
+            "SELECT chunk_id FROM chunks GROUP BY chunk_id HAVING COUNT(DISTINCT file_id) > 1",
+        ).map_err(SanchayCoreError::DbStatement)?;
+        let shared_chunk_ids: Vec<String> = shared_chunk_ids_stmt
+            .query_map(params![], |row| row.get(0))
+            .map_err(SanchayCoreError::DbQuery)?
+            .collect::<RusqliteResult<Vec<String>>>()
+            .map_err(SanchayCoreError::DbRowConversion)?;
+
+        // file_id -> (path, size), cached as we encounter file ids
+        let mut file_info: HashMap<i64, (PathBuf, u64)> = HashMap::new();
+        // (low_file_id, high_file_id) -> shared byte count
+        let mut shared_bytes: HashMap<(i64, i64), u64> = HashMap::new();
+
+        let mut chunk_rows_stmt = self.conn.prepare(
+            "SELECT DISTINCT file_id, len FROM chunks WHERE chunk_id = ?",
+        ).map_err(SanchayCoreError::DbStatement)?;
+
+        for chunk_id in shared_chunk_ids {
+            let rows: Vec<(i64, u64)> = chunk_rows_stmt
+                .query_map(params![chunk_id], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(SanchayCoreError::DbQuery)?
+                .collect::<RusqliteResult<Vec<(i64, u64)>>>()
+                .map_err(SanchayCoreError::DbRowConversion)?;
+
+            for i in 0..rows.len() {
+                for j in (i + 1)..rows.len() {
+                    let (file_a, len_a) = rows[i];
+                    let (file_b, _) = rows[j];
+                    let key = if file_a < file_b { (file_a, file_b) } else { (file_b, file_a) };
+                    *shared_bytes.entry(key).or_insert(0) += len_a;
+                }
+            }
+        }
+
+        let mut overlaps = Vec::with_capacity(shared_bytes.len());
+        for ((file_a, file_b), bytes) in shared_bytes {
+            let (path_a, size_a) = self.file_info_cached(&mut file_info, file_a)?;
+            let (path_b, size_b) = self.file_info_cached(&mut file_info, file_b)?;
+            overlaps.push(ChunkOverlap {
+                path_a,
+                path_b,
+                shared_bytes: bytes,
+                overlap_ratio_a: if size_a > 0 { bytes as f64 / size_a as f64 } else { 0.0 },
+                overlap_ratio_b: if size_b > 0 { bytes as f64 / size_b as f64 } else { 0.0 },
+            });
+        }
+        Ok(overlaps)
+    }
+
+    /// Fetches a file's `(path, size)` by id, populating `cache` on first lookup.
+    fn file_info_cached(&self, cache: &mut HashMap<i64, (PathBuf, u64)>, file_id: i64) -> Result<(PathBuf, u64), SanchayCoreError> {
+        if let Some(info) = cache.get(&file_id) {
+            return Ok(info.clone());
+        }
+        let info: (String, u64) = self.conn.query_row(
+            "SELECT path, size FROM files WHERE id = ?",
+            params![file_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).map_err(SanchayCoreError::DbQuery)?;
+        let info = (PathBuf::from(info.0), info.1);
+        cache.insert(file_id, info.clone());
+        Ok(info)
+    }
+
     /// Inserts a collection of `FileMetadata` entries into the database.
     /// Uses a transaction for performance and atomicity.
     /// If a file with the same `path` already exists, its metadata will be updated.
@@ -59,8 +296,8 @@ impl DatabaseManager {
         {
             // Use prepare_cached for performance when inserting multiple entries
             let mut stmt = tx.prepare_cached(
-                "INSERT OR REPLACE INTO files (path, file_name, size, checksum, modified_at, created_at)
-                 VALUES (?, ?, ?, ?, ?, ?)",
+                "INSERT OR REPLACE INTO files (path, file_name, size, checksum, modified_at, created_at, mime_type)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
             ).map_err(SanchayCoreError::DbStatement)?;
 
             for entry in metadata_entries {
@@ -71,6 +308,7 @@ impl DatabaseManager {
                     entry.checksum,
                     entry.modified_at,
                     entry.created_at,
+                    entry.mime_type,
                 ]).map_err(SanchayCoreError::DbInsert)?;
             }
         } // `stmt` is dropped here, releasing the borrow on `tx`
@@ -83,7 +321,7 @@ impl DatabaseManager {
     /// The results are ordered by checksum and then by path.
     pub fn get_duplicate_files(&self) -> Result<Vec<FileMetadata>, SanchayCoreError> {
         let mut stmt = self.conn.prepare(
-            "SELECT f.path, f.file_name, f.size, f.checksum, f.modified_at, f.created_at
+            "SELECT f.path, f.file_name, f.size, f.checksum, f.modified_at, f.created_at, f.mime_type
              FROM files f
              JOIN (
                  SELECT checksum
@@ -102,6 +340,34 @@ impl DatabaseManager {
                 checksum: row.get(3)?,
                 modified_at: row.get(4)?,
                 created_at: row.get(5)?,
+                mime_type: row.get(6)?,
+            })
+        }).map_err(SanchayCoreError::DbQuery)?;
+
+        let mut results = Vec::new();
+        for metadata_result in metadata_iter {
+            results.push(metadata_result.map_err(SanchayCoreError::DbRowConversion)?);
+        }
+        Ok(results)
+    }
+
+    /// Returns every stored file with the given `mime_type`, ordered by path. Backed by
+    /// `idx_files_mime_type`, so this stays cheap even on trees with millions of rows.
+    pub fn get_files_by_mime_type(&self, mime_type: &str) -> Result<Vec<FileMetadata>, SanchayCoreError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT path, file_name, size, checksum, modified_at, created_at, mime_type
+             FROM files WHERE mime_type = ? ORDER BY path",
+        ).map_err(SanchayCoreError::DbStatement)?;
+
+        let metadata_iter = stmt.query_map(params![mime_type], |row| {
+            Ok(FileMetadata {
+                path: PathBuf::from(row.get::<_, String>(0)?),
+                file_name: row.get(1)?,
+                size: row.get(2)?,
+                checksum: row.get(3)?,
+                modified_at: row.get(4)?,
+                created_at: row.get(5)?,
+                mime_type: row.get(6)?,
             })
         }).map_err(SanchayCoreError::DbQuery)?;
 
@@ -112,9 +378,109 @@ impl DatabaseManager {
         Ok(results)
     }
 
-    // Add other database interaction methods here as needed, e.g.,
-    // pub fn get_file_metadata_by_path(&self, path: &Path) -> Result<Option<FileMetadata>, SanchayCoreError> { ... }
-    // pub fn delete_file_metadata(&self, path: &Path) -> Result<(), SanchayCoreError> { ... }
+    /// Returns the number of stored files for each distinct `mime_type`, giving callers (e.g.
+    /// the PyO3 layer) an accurate content-type breakdown without fetching every row.
+    pub fn mime_type_counts(&self) -> Result<HashMap<String, u64>, SanchayCoreError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT mime_type, COUNT(*) FROM files GROUP BY mime_type",
+        ).map_err(SanchayCoreError::DbStatement)?;
+
+        let rows = stmt.query_map(params![], |row| Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?)))
+            .map_err(SanchayCoreError::DbQuery)?;
+
+        let mut counts = HashMap::new();
+        for row in rows {
+            let (mime_type, count) = row.map_err(SanchayCoreError::DbRowConversion)?;
+            counts.insert(mime_type, count);
+        }
+        Ok(counts)
+    }
+
+    /// Looks up a single file's metadata by its path.
+    pub fn get_file_metadata_by_path(&self, path: &Path) -> Result<Option<FileMetadata>, SanchayCoreError> {
+        self.conn
+            .query_row(
+                "SELECT path, file_name, size, checksum, modified_at, created_at, mime_type FROM files WHERE path = ?",
+                params![path.to_string_lossy()],
+                |row| {
+                    Ok(FileMetadata {
+                        path: PathBuf::from(row.get::<_, String>(0)?),
+                        file_name: row.get(1)?,
+                        size: row.get(2)?,
+                        checksum: row.get(3)?,
+                        modified_at: row.get(4)?,
+                        created_at: row.get(5)?,
+                        mime_type: row.get(6)?,
+                    })
+                },
+            )
+            .map(Some)
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                other => Err(SanchayCoreError::DbQuery(other)),
+            })
+    }
+
+    /// Removes a file's metadata (and any chunks referencing it, via `ON DELETE CASCADE`) by
+    /// path. A no-op if no metadata is stored for `path`.
+    pub fn delete_file_metadata(&self, path: &Path) -> Result<(), SanchayCoreError> {
+        self.conn
+            .execute("DELETE FROM files WHERE path = ?", params![path.to_string_lossy()])
+            .map_err(SanchayCoreError::DbStatement)?;
+        Ok(())
+    }
+
+    /// Returns every stored path that lies under `root` (inclusive). Used by
+    /// `incremental::incremental_scan` to find rows whose file no longer exists on disk.
+    pub fn all_paths_under(&self, root: &Path) -> Result<Vec<PathBuf>, SanchayCoreError> {
+        let mut stmt = self.conn.prepare("SELECT path FROM files").map_err(SanchayCoreError::DbStatement)?;
+
+        let path_iter = stmt
+            .query_map(params![], |row| row.get::<_, String>(0))
+            .map_err(SanchayCoreError::DbQuery)?;
+
+        let mut results = Vec::new();
+        for path_result in path_iter {
+            let path = PathBuf::from(path_result.map_err(SanchayCoreError::DbRowConversion)?);
+            if path.starts_with(root) {
+                results.push(path);
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Delegates to `DatabaseManager`'s own inherent methods so the SQLite backend can be used
+/// anywhere a `&dyn MetadataStore` / `impl MetadataStore` is expected, alongside
+/// `InMemoryMetadataStore` (see `store.rs`).
+impl MetadataStore for DatabaseManager {
+    fn insert_file_metadata(&self, metadata_entries: &[FileMetadata]) -> Result<(), SanchayCoreError> {
+        DatabaseManager::insert_file_metadata(self, metadata_entries)
+    }
+
+    fn get_duplicate_files(&self) -> Result<Vec<FileMetadata>, SanchayCoreError> {
+        DatabaseManager::get_duplicate_files(self)
+    }
+
+    fn get_file_metadata_by_path(&self, path: &Path) -> Result<Option<FileMetadata>, SanchayCoreError> {
+        DatabaseManager::get_file_metadata_by_path(self, path)
+    }
+
+    fn delete_file_metadata(&self, path: &Path) -> Result<(), SanchayCoreError> {
+        DatabaseManager::delete_file_metadata(self, path)
+    }
+
+    fn get_files_by_mime_type(&self, mime_type: &str) -> Result<Vec<FileMetadata>, SanchayCoreError> {
+        DatabaseManager::get_files_by_mime_type(self, mime_type)
+    }
+
+    fn mime_type_counts(&self) -> Result<HashMap<String, u64>, SanchayCoreError> {
+        DatabaseManager::mime_type_counts(self)
+    }
+
+    fn all_paths_under(&self, root: &Path) -> Result<Vec<PathBuf>, SanchayCoreError> {
+        DatabaseManager::all_paths_under(self, root)
+    }
 }
 
 #[cfg(test)]
@@ -129,6 +495,14 @@ mod tests {
         SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards").as_secs()
     }
 
+    #[test]
+    fn database_manager_satisfies_metadata_store_contract() -> RusqliteResult<()> {
+        let temp_db_file = NamedTempFile::new()?;
+        let manager = DatabaseManager::new(temp_db_file.path()).expect("Failed to create database manager");
+        crate::store::harness::run_contract_tests(&manager);
+        Ok(())
+    }
+
     #[test]
     fn test_database_manager_new_and_schema() -> RusqliteResult<()> {
         let temp_db_file = NamedTempFile::new()?;
@@ -166,6 +540,7 @@ mod tests {
                 checksum: "hash1".to_string(),
                 modified_at: now - 100,
                 created_at: now - 200,
+                mime_type: "text/plain".to_string(),
             },
             FileMetadata {
                 path: PathBuf::from("/x/y/file2.txt"),
@@ -174,6 +549,7 @@ mod tests {
                 checksum: "hash2".to_string(),
                 modified_at: now - 50,
                 created_at: now - 150,
+                mime_type: "text/plain".to_string(),
             },
             FileMetadata {
                 path: PathBuf::from("/c/d/file3.txt"),
@@ -182,6 +558,7 @@ mod tests {
                 checksum: "hash1".to_string(), // This is a duplicate hash of file1.txt
                 modified_at: now - 20,
                 created_at: now - 120,
+                mime_type: "image/png".to_string(),
             },
         ];
 
@@ -212,6 +589,7 @@ mod tests {
                 checksum: "new_hash1".to_string(), // Updated checksum
                 modified_at: now + 10,
                 created_at: now - 200, // Created time might remain the same
+                mime_type: "text/plain".to_string(),
             },
         ];
         manager.insert_file_metadata(&updated_metadata).expect("Failed to update metadata");
@@ -222,7 +600,7 @@ mod tests {
 
         // Retrieve the updated file and verify its new details
         let updated_file: FileMetadata = manager.conn.query_row(
-            "SELECT path, file_name, size, checksum, modified_at, created_at FROM files WHERE path = ?",
+            "SELECT path, file_name, size, checksum, modified_at, created_at, mime_type FROM files WHERE path = ?",
             params!["/a/b/file1.txt"],
             |row| Ok(FileMetadata {
                 path: PathBuf::from(row.get::<_, String>(0)?),
@@ -231,6 +609,7 @@ mod tests {
                 checksum: row.get(3)?,
                 modified_at: row.get(4)?,
                 created_at: row.get(5)?,
+                mime_type: row.get(6)?,
             }),
         )?;
         assert_eq!(updated_file.file_name, "file1_renamed.txt");
@@ -28,6 +28,43 @@ pub enum Error {
     /// or capturing a Python exception within Rust.
     #[error("Python error: {0}")]
     Python(#[from] PyErr),
+
+    /// Failed to open or connect to the SQLite database file.
+    #[error("Database connection error: {0}")]
+    DbConnection(rusqlite::Error),
+
+    /// Failed to create or migrate the database schema.
+    #[error("Database schema error: {0}")]
+    DbSchema(rusqlite::Error),
+
+    /// Failed to start, commit, or roll back a transaction.
+    #[error("Database transaction error: {0}")]
+    DbTransaction(rusqlite::Error),
+
+    /// Failed to prepare a SQL statement.
+    #[error("Database statement error: {0}")]
+    DbStatement(rusqlite::Error),
+
+    /// Failed to insert a row.
+    #[error("Database insert error: {0}")]
+    DbInsert(rusqlite::Error),
+
+    /// Failed to execute a query.
+    #[error("Database query error: {0}")]
+    DbQuery(rusqlite::Error),
+
+    /// Failed to convert a row into its Rust representation.
+    #[error("Database row conversion error: {0}")]
+    DbRowConversion(rusqlite::Error),
+
+    /// The on-disk schema's major version is newer than this binary supports.
+    #[error("Database schema version {on_disk_major}.{on_disk_minor} is newer than the supported {supported_major}.{supported_minor}")]
+    UnsupportedSchemaVersion {
+        on_disk_major: u32,
+        on_disk_minor: u32,
+        supported_major: u32,
+        supported_minor: u32,
+    },
 }
 
 /// A convenience type alias for results returned by functions in the `sanchay_core`.
@@ -35,6 +72,14 @@ pub enum Error {
 /// This simplifies error propagation using the `?` operator.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Alias used by modules (`database`, `walker`, `file_processor`) that predate the crate
+/// settling on the plain `Error` name; kept so their existing `SanchayCoreError`-qualified
+/// code doesn't need renaming everywhere at once.
+pub type SanchayCoreError = Error;
+
+/// Alias for the crate-facade re-export in `lib.rs`.
+pub type SanchayError = Error;
+
 /// Implements `IntoPy<PyErr>` for the custom `Error` enum.
 ///
 /// This conversion allows `sanchay_core::Error` instances to be transparently
@@ -50,6 +95,19 @@ impl IntoPy<PyErr> for Error {
             Error::ProcessingError(msg) => PyRuntimeError::new_err(format!("Processing Error: {}", msg)).into_py(py),
             // If the error is already a PyErr, just return it as is.
             Error::Python(err) => err,
+            Error::DbConnection(err) => PyRuntimeError::new_err(format!("Database connection error: {}", err)).into_py(py),
+            Error::DbSchema(err) => PyRuntimeError::new_err(format!("Database schema error: {}", err)).into_py(py),
+            Error::DbTransaction(err) => PyRuntimeError::new_err(format!("Database transaction error: {}", err)).into_py(py),
+            Error::DbStatement(err) => PyRuntimeError::new_err(format!("Database statement error: {}", err)).into_py(py),
+            Error::DbInsert(err) => PyRuntimeError::new_err(format!("Database insert error: {}", err)).into_py(py),
+            Error::DbQuery(err) => PyRuntimeError::new_err(format!("Database query error: {}", err)).into_py(py),
+            Error::DbRowConversion(err) => PyRuntimeError::new_err(format!("Database row conversion error: {}", err)).into_py(py),
+            Error::UnsupportedSchemaVersion { on_disk_major, on_disk_minor, supported_major, supported_minor } => {
+                PyRuntimeError::new_err(format!(
+                    "Database schema version {}.{} is newer than the supported {}.{}",
+                    on_disk_major, on_disk_minor, supported_major, supported_minor
+                )).into_py(py)
+            }
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,166 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::database::FileMetadata;
+use crate::error::{Result, SanchayCoreError};
+use crate::file_processor::{sniff_mime_type, FileProcessor};
+use crate::store::MetadataStore;
+use crate::walker;
+
+/// The outcome of an [`incremental_scan`], grouping every path under the scanned root by what
+/// changed since the store's previous record of it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeSet {
+    /// Paths with no prior row in the store.
+    pub added: Vec<PathBuf>,
+    /// Paths with a prior row whose size or `modified_at` no longer matches the file on disk.
+    pub modified: Vec<PathBuf>,
+    /// Paths with a prior row under `root` that no longer exist on disk.
+    pub deleted: Vec<PathBuf>,
+    /// Paths whose size and `modified_at` match the stored row exactly, so were not re-hashed.
+    pub unchanged: Vec<PathBuf>,
+}
+
+/// Rescans `root`, re-hashing and re-sniffing only the files whose size or modification time
+/// differ from what `store` already has recorded for them. This is the core win behind obnam's
+/// DB-speedup refactor and spacedrive's watcher-driven re-indexing: a repeat scan of an
+/// unchanged tree costs one `stat` per file rather than a full `blake3` hash of every byte.
+///
+/// `store` is updated in place: new and changed files are (re-)inserted with fresh metadata, and
+/// rows for paths under `root` that no longer exist on disk are deleted. The returned
+/// [`ChangeSet`] tells the caller exactly what happened, so e.g. a Python caller can diff two
+/// scans of the same tree without re-walking it itself.
+pub fn incremental_scan(store: &impl MetadataStore, root: &Path) -> Result<ChangeSet> {
+    let current_paths = walker::walk_directory_parallel(root, None)?;
+    let current_paths: HashSet<PathBuf> = current_paths.files.into_iter().collect();
+
+    let mut change_set = ChangeSet::default();
+
+    for path in &current_paths {
+        let stat = fs::metadata(path).map_err(SanchayCoreError::Io)?;
+        let size = stat.len();
+        let modified_at = system_time_to_unix_seconds(stat.modified().map_err(SanchayCoreError::Io)?);
+
+        match store.get_file_metadata_by_path(path)? {
+            Some(existing) if existing.size == size && existing.modified_at == modified_at => {
+                change_set.unchanged.push(path.clone());
+            }
+            Some(existing) => {
+                let created_at = existing.created_at;
+                store.insert_file_metadata(&[rehash(path, size, modified_at, created_at)?])?;
+                change_set.modified.push(path.clone());
+            }
+            None => {
+                let created_at = stat
+                    .created()
+                    .map(system_time_to_unix_seconds)
+                    .unwrap_or(modified_at);
+                store.insert_file_metadata(&[rehash(path, size, modified_at, created_at)?])?;
+                change_set.added.push(path.clone());
+            }
+        }
+    }
+
+    for stored_path in store.all_paths_under(root)? {
+        if !current_paths.contains(&stored_path) {
+            store.delete_file_metadata(&stored_path)?;
+            change_set.deleted.push(stored_path);
+        }
+    }
+
+    Ok(change_set)
+}
+
+/// Re-hashes and re-sniffs a single file whose stat no longer matches its stored row (or that
+/// has no stored row at all), producing the fresh [`FileMetadata`] to persist for it.
+fn rehash(path: &Path, size: u64, modified_at: u64, created_at: u64) -> Result<FileMetadata> {
+    let file_name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let checksum = FileProcessor::new(path.to_path_buf())
+        .compute_blake3_hash()
+        .map_err(SanchayCoreError::Io)?;
+    let mime_type = sniff_mime_type(path).map_err(SanchayCoreError::Io)?;
+
+    Ok(FileMetadata { path: path.to_path_buf(), file_name, size, checksum, modified_at, created_at, mime_type })
+}
+
+fn system_time_to_unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::InMemoryMetadataStore;
+    use std::fs as stdfs;
+    use std::thread;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    #[test]
+    fn first_scan_reports_everything_as_added() {
+        let dir = tempdir().unwrap();
+        stdfs::write(dir.path().join("a.txt"), b"hello").unwrap();
+        stdfs::write(dir.path().join("b.txt"), b"world").unwrap();
+
+        let store = InMemoryMetadataStore::new();
+        let change_set = incremental_scan(&store, dir.path()).unwrap();
+
+        assert_eq!(change_set.added.len(), 2);
+        assert!(change_set.modified.is_empty());
+        assert!(change_set.deleted.is_empty());
+        assert!(change_set.unchanged.is_empty());
+    }
+
+    #[test]
+    fn rescan_of_untouched_tree_reports_everything_as_unchanged() {
+        let dir = tempdir().unwrap();
+        stdfs::write(dir.path().join("a.txt"), b"hello").unwrap();
+
+        let store = InMemoryMetadataStore::new();
+        incremental_scan(&store, dir.path()).unwrap();
+        let change_set = incremental_scan(&store, dir.path()).unwrap();
+
+        assert!(change_set.added.is_empty());
+        assert!(change_set.modified.is_empty());
+        assert!(change_set.deleted.is_empty());
+        assert_eq!(change_set.unchanged.len(), 1);
+    }
+
+    #[test]
+    fn edited_file_is_reported_as_modified_with_updated_checksum() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.txt");
+        stdfs::write(&path, b"hello").unwrap();
+
+        let store = InMemoryMetadataStore::new();
+        incremental_scan(&store, dir.path()).unwrap();
+
+        // Ensure the new mtime is observably different from the first write.
+        thread::sleep(Duration::from_millis(1100));
+        stdfs::write(&path, b"hello, much longer now").unwrap();
+
+        let change_set = incremental_scan(&store, dir.path()).unwrap();
+        assert_eq!(change_set.modified, vec![path.clone()]);
+
+        let updated = store.get_file_metadata_by_path(&path).unwrap().unwrap();
+        assert_eq!(updated.size, b"hello, much longer now".len() as u64);
+    }
+
+    #[test]
+    fn removed_file_is_reported_as_deleted_and_dropped_from_the_store() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.txt");
+        stdfs::write(&path, b"hello").unwrap();
+
+        let store = InMemoryMetadataStore::new();
+        incremental_scan(&store, dir.path()).unwrap();
+
+        stdfs::remove_file(&path).unwrap();
+        let change_set = incremental_scan(&store, dir.path()).unwrap();
+
+        assert_eq!(change_set.deleted, vec![path.clone()]);
+        assert!(store.get_file_metadata_by_path(&path).unwrap().is_none());
+    }
+}
@@ -1,12 +1,22 @@
+pub mod chunker;
 pub mod error;
 pub mod walker;
 pub mod file_processor;
 pub mod database;
+pub mod store;
+pub mod incremental;
 pub mod bindings; // This module contains the PyO3 module definition and Python-callable functions
 
 // Re-export the custom error type and Result alias for convenient access throughout the crate
 pub use error::{SanchayError, Result};
 
+// Re-export the pluggable metadata-persistence trait and its in-memory implementation so callers
+// can depend on `sanchay_core::MetadataStore` without reaching into the `store` module directly.
+pub use store::{InMemoryMetadataStore, MetadataStore};
+
+// Re-export the incremental-rescan entry point and its change-set type.
+pub use incremental::{incremental_scan, ChangeSet};
+
 // You might add other public re-exports here if internal Rust modules need to expose
 // specific types or functions directly through the `sanchay_core` crate facade.
 // For example:
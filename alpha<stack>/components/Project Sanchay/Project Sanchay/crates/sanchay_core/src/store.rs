@@ -0,0 +1,222 @@
+//! `MetadataStore` abstracts file-metadata persistence behind a single interface so the crate
+//! isn't hard-wired to SQLite, mirroring how OSM's common layer abstracts persistence behind a
+//! base interface with local, in-memory, and remote implementations. `DatabaseManager` (see
+//! `database.rs`) is the on-disk, SQLite-backed implementation used in production;
+//! `InMemoryMetadataStore` below is a `HashMap`-backed implementation useful for tests and
+//! ephemeral scans that don't want a database file on disk at all.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::database::FileMetadata;
+use crate::error::Result;
+
+/// Persists and queries file metadata. Implemented by the SQLite-backed `DatabaseManager` and
+/// the in-memory `InMemoryMetadataStore`; callers (including the PyO3 layer) can depend on this
+/// trait instead of a concrete backend.
+pub trait MetadataStore {
+    /// Inserts or replaces metadata for each entry, keyed by `FileMetadata::path`.
+    fn insert_file_metadata(&self, metadata_entries: &[FileMetadata]) -> Result<()>;
+
+    /// Returns every stored file whose checksum is shared by at least one other file, ordered
+    /// by checksum then path.
+    fn get_duplicate_files(&self) -> Result<Vec<FileMetadata>>;
+
+    /// Looks up a single file's metadata by its path.
+    fn get_file_metadata_by_path(&self, path: &Path) -> Result<Option<FileMetadata>>;
+
+    /// Removes a file's metadata by its path. A no-op if no metadata is stored for `path`.
+    fn delete_file_metadata(&self, path: &Path) -> Result<()>;
+
+    /// Returns every stored file with the given `mime_type`, ordered by path.
+    fn get_files_by_mime_type(&self, mime_type: &str) -> Result<Vec<FileMetadata>>;
+
+    /// Returns the number of stored files for each distinct `mime_type`.
+    fn mime_type_counts(&self) -> Result<HashMap<String, u64>>;
+
+    /// Returns every stored path that lies under `root` (inclusive). Used by
+    /// `incremental::incremental_scan` to find rows whose file no longer exists on disk.
+    fn all_paths_under(&self, root: &Path) -> Result<Vec<PathBuf>>;
+}
+
+/// A `HashMap`-backed `MetadataStore` with no on-disk persistence, useful for unit tests and
+/// ephemeral scans (e.g. a one-shot duplicate check) that don't want to manage a SQLite file.
+#[derive(Default)]
+pub struct InMemoryMetadataStore {
+    files: Mutex<HashMap<PathBuf, FileMetadata>>,
+}
+
+impl InMemoryMetadataStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        InMemoryMetadataStore::default()
+    }
+}
+
+impl MetadataStore for InMemoryMetadataStore {
+    fn insert_file_metadata(&self, metadata_entries: &[FileMetadata]) -> Result<()> {
+        let mut files = self.files.lock().expect("in-memory metadata store mutex poisoned");
+        for entry in metadata_entries {
+            files.insert(entry.path.clone(), entry.clone());
+        }
+        Ok(())
+    }
+
+    fn get_duplicate_files(&self) -> Result<Vec<FileMetadata>> {
+        let files = self.files.lock().expect("in-memory metadata store mutex poisoned");
+
+        let mut by_checksum: HashMap<&str, Vec<&FileMetadata>> = HashMap::new();
+        for metadata in files.values() {
+            by_checksum.entry(metadata.checksum.as_str()).or_default().push(metadata);
+        }
+
+        let mut duplicates: Vec<FileMetadata> = by_checksum
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .flatten()
+            .cloned()
+            .collect();
+        duplicates.sort_by(|a, b| a.checksum.cmp(&b.checksum).then_with(|| a.path.cmp(&b.path)));
+        Ok(duplicates)
+    }
+
+    fn get_file_metadata_by_path(&self, path: &Path) -> Result<Option<FileMetadata>> {
+        let files = self.files.lock().expect("in-memory metadata store mutex poisoned");
+        Ok(files.get(path).cloned())
+    }
+
+    fn delete_file_metadata(&self, path: &Path) -> Result<()> {
+        let mut files = self.files.lock().expect("in-memory metadata store mutex poisoned");
+        files.remove(path);
+        Ok(())
+    }
+
+    fn get_files_by_mime_type(&self, mime_type: &str) -> Result<Vec<FileMetadata>> {
+        let files = self.files.lock().expect("in-memory metadata store mutex poisoned");
+        let mut matches: Vec<FileMetadata> = files
+            .values()
+            .filter(|metadata| metadata.mime_type == mime_type)
+            .cloned()
+            .collect();
+        matches.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(matches)
+    }
+
+    fn mime_type_counts(&self) -> Result<HashMap<String, u64>> {
+        let files = self.files.lock().expect("in-memory metadata store mutex poisoned");
+        let mut counts = HashMap::new();
+        for metadata in files.values() {
+            *counts.entry(metadata.mime_type.clone()).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    fn all_paths_under(&self, root: &Path) -> Result<Vec<PathBuf>> {
+        let files = self.files.lock().expect("in-memory metadata store mutex poisoned");
+        Ok(files.keys().filter(|path| path.starts_with(root)).cloned().collect())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod harness {
+    //! Shared behavioral test suite run against every `MetadataStore` implementation, so a new
+    //! backend only has to call `run_contract_tests` to inherit the same coverage as
+    //! `DatabaseManager` and `InMemoryMetadataStore`.
+    use super::*;
+
+    fn sample_metadata(now: u64) -> Vec<FileMetadata> {
+        vec![
+            FileMetadata {
+                path: PathBuf::from("/a/b/file1.txt"),
+                file_name: "file1.txt".to_string(),
+                size: 100,
+                checksum: "hash1".to_string(),
+                modified_at: now - 100,
+                created_at: now - 200,
+                mime_type: "text/plain".to_string(),
+            },
+            FileMetadata {
+                path: PathBuf::from("/x/y/file2.txt"),
+                file_name: "file2.txt".to_string(),
+                size: 200,
+                checksum: "hash2".to_string(),
+                modified_at: now - 50,
+                created_at: now - 150,
+                mime_type: "text/plain".to_string(),
+            },
+            FileMetadata {
+                path: PathBuf::from("/c/d/file3.txt"),
+                file_name: "file3.txt".to_string(),
+                size: 300,
+                checksum: "hash1".to_string(), // duplicate of file1.txt
+                modified_at: now - 20,
+                created_at: now - 120,
+                mime_type: "image/png".to_string(),
+            },
+        ]
+    }
+
+    pub(crate) fn now_as_unix_seconds() -> u64 {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards").as_secs()
+    }
+
+    /// Exercises insert/duplicate-detection/lookup/delete against any `MetadataStore`.
+    pub(crate) fn run_contract_tests(store: &impl MetadataStore) {
+        let now = now_as_unix_seconds();
+        let metadata = sample_metadata(now);
+        store.insert_file_metadata(&metadata).expect("insert should succeed");
+
+        let mut duplicates = store.get_duplicate_files().expect("get_duplicate_files should succeed");
+        duplicates.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(duplicates.len(), 2, "expected file1.txt and file3.txt to be reported as duplicates");
+        assert_eq!(duplicates[0].file_name, "file1.txt");
+        assert_eq!(duplicates[1].file_name, "file3.txt");
+
+        let fetched = store
+            .get_file_metadata_by_path(Path::new("/x/y/file2.txt"))
+            .expect("get_file_metadata_by_path should succeed")
+            .expect("file2.txt should be present");
+        assert_eq!(fetched.checksum, "hash2");
+        assert!(store
+            .get_file_metadata_by_path(Path::new("/not/a/real/path"))
+            .expect("lookup of a missing path should succeed")
+            .is_none());
+
+        let text_files = store
+            .get_files_by_mime_type("text/plain")
+            .expect("get_files_by_mime_type should succeed");
+        assert_eq!(text_files.len(), 2, "file1.txt and file2.txt are both text/plain");
+
+        let counts = store.mime_type_counts().expect("mime_type_counts should succeed");
+        assert_eq!(counts.get("text/plain"), Some(&2));
+        assert_eq!(counts.get("image/png"), Some(&1));
+
+        let under_xy = store
+            .all_paths_under(Path::new("/x/y"))
+            .expect("all_paths_under should succeed");
+        assert_eq!(under_xy, vec![PathBuf::from("/x/y/file2.txt")]);
+
+        store
+            .delete_file_metadata(Path::new("/a/b/file1.txt"))
+            .expect("delete_file_metadata should succeed");
+        assert!(store
+            .get_file_metadata_by_path(Path::new("/a/b/file1.txt"))
+            .expect("lookup after delete should succeed")
+            .is_none());
+
+        let duplicates_after_delete = store
+            .get_duplicate_files()
+            .expect("get_duplicate_files should succeed after delete");
+        assert!(
+            duplicates_after_delete.is_empty(),
+            "file3.txt should no longer have a duplicate after file1.txt was deleted"
+        );
+    }
+
+    #[test]
+    fn in_memory_store_satisfies_contract() {
+        run_contract_tests(&InMemoryMetadataStore::new());
+    }
+}
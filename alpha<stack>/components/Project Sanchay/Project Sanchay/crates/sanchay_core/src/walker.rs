@@ -1,24 +1,208 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use walkdir::{WalkDir, DirEntry};
 use rayon::prelude::*;
 
+/// `globset` backs `GlobMatcher`'s pattern compilation; it must be declared as a dependency in
+/// `crates/sanchay_core/Cargo.toml`.
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+/// Backs `ScanCache`'s on-disk (de)serialization; `serde`'s `derive` feature must be declared as
+/// a dependency in `crates/sanchay_core/Cargo.toml` alongside the `serde_json` already used for
+/// database serialization errors in `error.rs`.
+use serde::{Deserialize, Serialize};
+
 use crate::error::{SanchayCoreError, Result};
 
+/// Abstraction over filesystem access, modeled on Mercurial's opener/vfs layering: everything
+/// `walk_directory_parallel` needs to know about a path — whether it exists, what kind of entry
+/// it is, what its children are — goes through a `&dyn Vfs` rather than calling `std::fs`
+/// directly. [`LocalVfs`] is the production implementation; tests can swap in an in-memory mock
+/// to exercise traversal and permission-denied handling without `tempfile` or real `chmod` calls.
+///
+/// Note: this crate has no `sanitize_and_constrain_path` function to rewrite against `Vfs` — that
+/// path-constraint helper lives in the separate `vegafs-core` crate, not here, so only the walker
+/// side of this refactor applies to `sanchay_core`.
+pub trait Vfs: Send + Sync {
+    /// Resolves `path` to its canonical, symlink-free absolute form.
+    fn canonicalize(&self, path: &Path) -> std::io::Result<PathBuf>;
+    /// Stats `path`, following a trailing symlink to its target.
+    fn metadata(&self, path: &Path) -> std::io::Result<VfsMetadata>;
+    /// Stats `path` without following a trailing symlink.
+    fn symlink_metadata(&self, path: &Path) -> std::io::Result<VfsMetadata>;
+    /// Lists the immediate children of directory `path`, in arbitrary order.
+    fn read_dir(&self, path: &Path) -> std::io::Result<Vec<PathBuf>>;
+}
+
+/// The entry kind a [`Vfs`] reports for a path — deliberately just the three kinds
+/// `walk_directory_parallel` cares about, rather than `std::fs::Metadata` itself, since the
+/// latter wraps a platform `stat` struct that a non-local backend (an archive, a remote store)
+/// has no way to produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VfsMetadata {
+    File,
+    Directory,
+    Other,
+}
+
+impl VfsMetadata {
+    pub fn is_file(&self) -> bool {
+        matches!(self, VfsMetadata::File)
+    }
+
+    pub fn is_dir(&self) -> bool {
+        matches!(self, VfsMetadata::Directory)
+    }
+}
+
+/// The production [`Vfs`] implementation, backed directly by `std::fs`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalVfs;
+
+impl LocalVfs {
+    fn classify(meta: &std::fs::Metadata) -> VfsMetadata {
+        if meta.is_file() {
+            VfsMetadata::File
+        } else if meta.is_dir() {
+            VfsMetadata::Directory
+        } else {
+            VfsMetadata::Other
+        }
+    }
+}
+
+impl Vfs for LocalVfs {
+    fn canonicalize(&self, path: &Path) -> std::io::Result<PathBuf> {
+        path.canonicalize()
+    }
+
+    fn metadata(&self, path: &Path) -> std::io::Result<VfsMetadata> {
+        std::fs::metadata(path).map(|meta| Self::classify(&meta))
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> std::io::Result<VfsMetadata> {
+        std::fs::symlink_metadata(path).map(|meta| Self::classify(&meta))
+    }
+
+    fn read_dir(&self, path: &Path) -> std::io::Result<Vec<PathBuf>> {
+        std::fs::read_dir(path)?.map(|entry| entry.map(|e| e.path())).collect()
+    }
+}
+
+/// A composable predicate over file paths, modeled on Mercurial's matcher stack: small building
+/// blocks (`GlobMatcher`) combine via `IntersectionMatcher`/`DifferenceMatcher` into whatever
+/// inclusion/exclusion logic a caller needs, keeping `walk_directory_parallel` itself ignorant of
+/// glob syntax entirely.
+pub trait Matcher: Send + Sync {
+    /// Returns `true` if `path` (relative to the walk's root) should be included in the results.
+    fn matches(&self, path: &Path) -> bool;
+}
+
+/// Matches paths against a fixed set of glob/gitignore-style patterns (e.g. `"src/**/*.rs"`),
+/// compiled once via `globset` for fast repeated matching across a whole tree.
+pub struct GlobMatcher {
+    globset: GlobSet,
+}
+
+impl GlobMatcher {
+    /// Compiles `patterns` into a single `GlobMatcher`. An invalid pattern surfaces as a
+    /// `SanchayCoreError::ProcessingError` rather than panicking.
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = Glob::new(pattern)
+                .map_err(|e| SanchayCoreError::ProcessingError(format!("Invalid glob pattern '{}': {}", pattern, e)))?;
+            builder.add(glob);
+        }
+        let globset = builder
+            .build()
+            .map_err(|e| SanchayCoreError::ProcessingError(format!("Failed to compile glob patterns: {}", e)))?;
+        Ok(GlobMatcher { globset })
+    }
+}
+
+impl Matcher for GlobMatcher {
+    fn matches(&self, path: &Path) -> bool {
+        self.globset.is_match(path)
+    }
+}
+
+/// Matches paths matched by both `a` and `b` (logical AND).
+pub struct IntersectionMatcher {
+    a: Box<dyn Matcher>,
+    b: Box<dyn Matcher>,
+}
+
+impl IntersectionMatcher {
+    pub fn new(a: Box<dyn Matcher>, b: Box<dyn Matcher>) -> Self {
+        IntersectionMatcher { a, b }
+    }
+}
+
+impl Matcher for IntersectionMatcher {
+    fn matches(&self, path: &Path) -> bool {
+        self.a.matches(path) && self.b.matches(path)
+    }
+}
+
+/// Matches paths matched by `included` but not by `excluded` — e.g. "everything under `src/`
+/// except `tests/`".
+pub struct DifferenceMatcher {
+    included: Box<dyn Matcher>,
+    excluded: Box<dyn Matcher>,
+}
+
+impl DifferenceMatcher {
+    pub fn new(included: Box<dyn Matcher>, excluded: Box<dyn Matcher>) -> Self {
+        DifferenceMatcher { included, excluded }
+    }
+}
+
+impl Matcher for DifferenceMatcher {
+    fn matches(&self, path: &Path) -> bool {
+        self.included.matches(path) && !self.excluded.matches(path)
+    }
+}
+
+/// A single traversal error encountered while walking a directory (e.g. permission denied),
+/// recorded instead of printed so library consumers can inspect or test against it.
+#[derive(Debug, Clone)]
+pub struct WalkWarning {
+    /// The path the error was reported against, if `walkdir` could determine one.
+    pub path: PathBuf,
+    /// The underlying I/O error kind, if the error wrapped one.
+    pub kind: std::io::ErrorKind,
+    /// The error's display text, for logging or surfacing to a caller verbatim.
+    pub message: String,
+}
+
+/// The result of a [`walk_directory_parallel`] call: every regular file found, plus every
+/// [`WalkWarning`] recorded along the way instead of being printed to stderr.
+#[derive(Debug, Clone, Default)]
+pub struct WalkOutcome {
+    pub files: Vec<PathBuf>,
+    pub warnings: Vec<WalkWarning>,
+}
+
 /// Recursively walks a directory, collecting paths to all files within it.
 ///
 /// This function utilizes `walkdir` for efficient directory traversal and `rayon`
 /// for parallel processing of directory entries, ensuring high performance.
 /// It filters out directories and only returns paths to regular files.
-/// Errors encountered during directory traversal (e.g., permission denied) are
-/// logged to stderr and skipped, allowing the process to continue.
+/// Errors encountered during directory traversal (e.g., permission denied) are accumulated into
+/// the returned [`WalkOutcome::warnings`] instead of being printed, so a caller (or a test) can
+/// inspect exactly what went wrong and where.
 ///
 /// # Arguments
 /// * `root_path` - A reference to the starting `Path` for the traversal.
+/// * `matcher` - An optional [`Matcher`], applied to each entry's path relative to `root_path`;
+///   entries it rejects are dropped before ever reaching the result `Vec`. `None` keeps every
+///   regular file, matching this function's original behavior.
 ///
 /// # Returns
-/// A `Result` containing a `Vec<PathBuf>` of all discovered file paths,
-/// or a `SanchayCoreError` if the initial `root_path` does not exist or
-/// is not a valid directory/file.
+/// A `Result` containing a [`WalkOutcome`] with every discovered file path and every traversal
+/// warning, or a `SanchayCoreError` if the initial `root_path` does not exist.
 ///
 /// # Examples
 /// ```no_run
@@ -40,59 +224,395 @@ use crate::error::{SanchayCoreError, Result};
 /// #
 /// # let test_dir = create_test_dir();
 /// #
-/// let file_paths = walker::walk_directory_parallel(&test_dir).unwrap();
-/// assert_eq!(file_paths.len(), 2);
-/// assert!(file_paths.iter().any(|p| p.ends_with("file1.txt")));
-/// assert!(file_paths.iter().any(|p| p.ends_with("file2.log")));
+/// let outcome = walker::walk_directory_parallel(&test_dir, None).unwrap();
+/// assert_eq!(outcome.files.len(), 2);
+/// assert!(outcome.files.iter().any(|p| p.ends_with("file1.txt")));
+/// assert!(outcome.files.iter().any(|p| p.ends_with("file2.log")));
+/// assert!(outcome.warnings.is_empty());
 /// # let _ = std::fs::remove_dir_all(&test_dir);
 /// ```
-pub fn walk_directory_parallel(root_path: &Path) -> Result<Vec<PathBuf>> {
+pub fn walk_directory_parallel(root_path: &Path, matcher: Option<&dyn Matcher>) -> Result<WalkOutcome> {
+    walk_directory_parallel_with_vfs(root_path, matcher, &LocalVfs)
+}
+
+/// Like [`walk_directory_parallel`], but against an arbitrary [`Vfs`] instead of the real
+/// filesystem — the seam that makes this traversal logic unit-testable without `tempfile` or
+/// real permission-bit hacks. `walk_directory_parallel` is just this function called with
+/// [`LocalVfs`].
+pub fn walk_directory_parallel_with_vfs(
+    root_path: &Path,
+    matcher: Option<&dyn Matcher>,
+    vfs: &dyn Vfs,
+) -> Result<WalkOutcome> {
+    let root_meta = match vfs.metadata(root_path) {
+        Ok(meta) => meta,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(SanchayCoreError::Io(
+                std::io::Error::new(std::io::ErrorKind::NotFound, "Root path does not exist.")
+            ));
+        }
+        Err(err) => return Err(SanchayCoreError::Io(err)),
+    };
+
+    if root_meta.is_file() {
+        // If the path points to a single file, just return that file's path (subject to the
+        // matcher, evaluated against the file's own name since there's no root to be relative to).
+        let file_name = root_path.file_name().map(Path::new).unwrap_or(root_path);
+        let files = match matcher {
+            Some(matcher) if !matcher.matches(file_name) => Vec::new(),
+            _ => vec![root_path.to_path_buf()],
+        };
+        return Ok(WalkOutcome { files, warnings: Vec::new() });
+    }
+
+    // The walk itself is a sequential depth-first descent through `vfs` — a `Vfs` backend (e.g.
+    // an archive or a remote store) can't be assumed to support the same cheap concurrent
+    // directory reads `WalkDir`+`par_bridge` relied on for the real filesystem. Matching against
+    // `matcher`, which is pure CPU work, is still done in parallel via rayon below.
+    let mut entries = Vec::new();
+    let mut warnings = Vec::new();
+    collect_entries(vfs, root_path, &mut entries, &mut warnings);
+
+    let files: Vec<PathBuf> = entries
+        .into_par_iter()
+        .filter(|path| {
+            let relative = path.strip_prefix(root_path).unwrap_or(path);
+            match matcher {
+                Some(matcher) => matcher.matches(relative),
+                None => true,
+            }
+        })
+        .collect();
+
+    Ok(WalkOutcome { files, warnings })
+}
+
+/// Depth-first walk of `dir` via `vfs`, appending every regular file found to `files`. A
+/// directory that fails to list (e.g. permission denied) is recorded as a [`WalkWarning`] instead
+/// of aborting the whole traversal; symlinks and other special files are silently skipped, same
+/// as the old `WalkDir`-based traversal (which never follows symlinks by default).
+fn collect_entries(vfs: &dyn Vfs, dir: &Path, files: &mut Vec<PathBuf>, warnings: &mut Vec<WalkWarning>) {
+    let children = match vfs.read_dir(dir) {
+        Ok(children) => children,
+        Err(err) => {
+            warnings.push(WalkWarning { path: dir.to_path_buf(), kind: err.kind(), message: err.to_string() });
+            return;
+        }
+    };
+
+    for child in children {
+        match vfs.symlink_metadata(&child) {
+            Ok(meta) if meta.is_dir() => collect_entries(vfs, &child, files, warnings),
+            Ok(meta) if meta.is_file() => files.push(child),
+            Ok(_) => {}
+            Err(err) => warnings.push(WalkWarning { path: child, kind: err.kind(), message: err.to_string() }),
+        }
+    }
+}
+
+/// Why a path encountered during a walk was rejected from the regular-file result list of
+/// [`walk_directory_classified`], derived from the entry's `file_type()` and, on unix, the
+/// `st_mode` bits exposed via `std::os::unix::fs::FileTypeExt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadType {
+    CharacterDevice,
+    BlockDevice,
+    Fifo,
+    Socket,
+    Directory,
+    Symlink,
+    Unknown,
+}
+
+impl BadType {
+    /// A short, stable string form, for surfacing to callers (e.g. the Python bindings) that want
+    /// a human-readable reason rather than matching on the enum itself.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BadType::CharacterDevice => "character_device",
+            BadType::BlockDevice => "block_device",
+            BadType::Fifo => "fifo",
+            BadType::Socket => "socket",
+            BadType::Directory => "directory",
+            BadType::Symlink => "symlink",
+            BadType::Unknown => "unknown",
+        }
+    }
+
+    fn classify(entry: &DirEntry) -> BadType {
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            return BadType::Directory;
+        }
+        if file_type.is_symlink() {
+            return BadType::Symlink;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+            if file_type.is_block_device() {
+                return BadType::BlockDevice;
+            }
+            if file_type.is_char_device() {
+                return BadType::CharacterDevice;
+            }
+            if file_type.is_fifo() {
+                return BadType::Fifo;
+            }
+            if file_type.is_socket() {
+                return BadType::Socket;
+            }
+        }
+        BadType::Unknown
+    }
+}
+
+/// Like [`walk_directory_parallel`], but instead of silently dropping anything that isn't a
+/// regular file, reports every rejected path alongside the [`BadType`] that explains why it was
+/// rejected — a symlink, a FIFO, a socket, a block/char device, or simply a directory. Useful for
+/// callers (e.g. a backup or dedup tool) that want to warn about special files they can't process
+/// rather than have them silently vanish from the result.
+pub fn walk_directory_classified(root_path: &Path) -> Result<(Vec<PathBuf>, Vec<(PathBuf, BadType)>)> {
     if !root_path.exists() {
-        return Err(SanchayCoreError::IOError(
+        return Err(SanchayCoreError::Io(
             std::io::Error::new(std::io::ErrorKind::NotFound, "Root path does not exist.")
         ));
     }
 
-    if root_path.is_file() {
-        // If the path points to a single file, just return that file's path.
-        // This handles cases where the user might specify a file directly instead of a directory.
-        return Ok(vec![root_path.to_path_buf()]);
-    }
-
-    // Initialize WalkDir for the given path.
-    // into_iter() consumes it and returns a sequential iterator over DirEntry or Error.
-    // par_bridge() bridges the sequential iterator to a parallel one for Rayon.
-    let files: Vec<PathBuf> = WalkDir::new(root_path)
+    let results: Vec<std::result::Result<PathBuf, (PathBuf, BadType)>> = WalkDir::new(root_path)
         .into_iter()
-        .par_bridge() // Converts `Iterator<Item = Result<DirEntry>>` into `ParallelIterator<Item = Result<DirEntry>>`
-        .filter_map(|entry_result| {
-            match entry_result {
-                Ok(entry) => {
-                    // Check if the entry is a file. Symlinks are followed by default and their target type is checked.
-                    if entry.file_type().is_file() {
-                        Some(entry.into_path())
-                    } else {
-                        // Skip directories, symlinks to directories, or other special files
-                        None
-                    }
+        .par_bridge()
+        .filter_map(|entry_result| match entry_result {
+            Ok(entry) => {
+                if entry.path() == root_path {
+                    // The root entry itself is just the starting point, not a rejected child.
+                    return None;
                 }
-                Err(err) => {
-                    // Log errors encountered during traversal (e.g., permission denied)
-                    // and continue with other entries. A more robust solution might
-                    // collect these errors or report them back to the caller.
-                    eprintln!("Error traversing path {:?}: {}", err.path().unwrap_or_default(), err);
-                    None // Skip this entry
+                if entry.file_type().is_file() {
+                    Some(Ok(entry.into_path()))
+                } else {
+                    let bad_type = BadType::classify(&entry);
+                    Some(Err((entry.into_path(), bad_type)))
                 }
             }
+            Err(err) => {
+                eprintln!("Error traversing path {:?}: {}", err.path().unwrap_or_default(), err);
+                None
+            }
         })
-        .collect(); // Collect all processed file paths into a Vec
+        .collect();
 
-    Ok(files)
+    let mut files = Vec::new();
+    let mut rejected = Vec::new();
+    for result in results {
+        match result {
+            Ok(path) => files.push(path),
+            Err(entry) => rejected.push(entry),
+        }
+    }
+
+    Ok((files, rejected))
+}
+
+/// A modification time recorded with whole-second-plus-nanosecond granularity, flagged
+/// `second_ambiguous` when it was captured in the same wall-clock second as the scan that
+/// recorded it. On filesystems with one-second mtime resolution, a file edited again within that
+/// same second would otherwise report an mtime indistinguishable from the cached sample, so
+/// [`scan_directory_incremental`] treats any ambiguous cached entry as unconditionally dirty
+/// rather than trusting it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TruncatedTimestamp {
+    pub seconds: u64,
+    pub nanos: u32,
+    pub second_ambiguous: bool,
+}
+
+impl TruncatedTimestamp {
+    /// Captures `mtime`, marking it `second_ambiguous` if its whole-second value equals the
+    /// current wall-clock second — i.e. the file could still be edited again before this second
+    /// elapses, after this very sample was taken.
+    pub fn capture(mtime: SystemTime) -> Self {
+        let since_epoch = mtime.duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+        let now_since_epoch = SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+        TruncatedTimestamp {
+            seconds: since_epoch.as_secs(),
+            nanos: since_epoch.subsec_nanos(),
+            second_ambiguous: since_epoch.as_secs() == now_since_epoch.as_secs(),
+        }
+    }
+}
+
+impl PartialEq for TruncatedTimestamp {
+    /// Two timestamps compare equal only if neither is ambiguous and their seconds/nanos match
+    /// exactly; an ambiguous timestamp never compares equal, even to an identical one, since the
+    /// file it came from might have changed again within the same second.
+    fn eq(&self, other: &Self) -> bool {
+        if self.second_ambiguous || other.second_ambiguous {
+            return false;
+        }
+        self.seconds == other.seconds && self.nanos == other.nanos
+    }
+}
+
+/// A single file's cached identity within a [`CachedDirEntry`] — just enough to decide, on the
+/// next scan, whether the file needs re-hashing at all.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedFileEntry {
+    pub file_name: String,
+    pub size: u64,
+    pub modified_at: TruncatedTimestamp,
+    pub checksum: String,
+}
+
+/// A directory's mtime at the time it was last scanned, plus the child files it held then. While
+/// the directory's mtime on disk still matches `dir_modified_at`, the checksums in `children` are
+/// reused for files whose stat still matches; the directory is listed afresh either way.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedDirEntry {
+    pub dir_modified_at: u64,
+    pub children: Vec<CachedFileEntry>,
+}
+
+/// A sidecar cache of directory mtimes and their child file entries, persisted as JSON so a
+/// repeat [`scan_directory_incremental`] can skip re-hashing files that haven't changed since the
+/// cache was written; every directory is still walked. Keyed by directory path relative to the scan root,
+/// mirroring the dirstate-v2 trick of caching a directory's mtime alongside its membership.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanCache {
+    pub dirs: HashMap<PathBuf, CachedDirEntry>,
+}
+
+impl ScanCache {
+    /// Loads a cache from `path`, returning an empty cache if no file exists there yet (e.g. on
+    /// the very first scan).
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(ScanCache::default());
+        }
+        let bytes = std::fs::read(path).map_err(SanchayCoreError::Io)?;
+        serde_json::from_slice(&bytes).map_err(SanchayCoreError::Serialization)
+    }
+
+    /// Persists the cache to `path` as JSON, overwriting whatever was there before.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let bytes = serde_json::to_vec(self).map_err(SanchayCoreError::Serialization)?;
+        std::fs::write(path, bytes).map_err(SanchayCoreError::Io)
+    }
+}
+
+/// Rescans `root`, consulting a [`ScanCache`] persisted at `cache_path` to skip re-hashing files
+/// that haven't changed. Every directory is still listed with `read_dir` and every subdirectory
+/// descended, and every file is stat-ed and has its MIME type sniffed on each scan; unchanged
+/// directories are not skipped. A directory's mtime is compared against the cached value: if it
+/// differs (a child was added, removed, or renamed), every file in it is re-hashed. If it
+/// matches, each child's own size and mtime are still checked individually, since an in-place
+/// content edit doesn't always bubble up to the parent directory's mtime on every filesystem,
+/// and only a child whose stat still matches the cache reuses its cached checksum. A child whose
+/// cached [`TruncatedTimestamp`] is `second_ambiguous` is always treated as dirty, since its mtime
+/// alone can't rule out a same-second edit. The updated cache is written back to `cache_path`
+/// before returning.
+///
+/// `algorithm` is accepted for consistency with the rest of the checksum-algorithm surface (see
+/// `ChecksumAlgorithm`); like `incremental::incremental_scan`, every checksum is currently
+/// computed via `FileProcessor::compute_blake3_hash`.
+pub fn scan_directory_incremental(
+    root: &Path,
+    cache_path: &Path,
+    algorithm: crate::file_processor::ChecksumAlgorithm,
+) -> Result<Vec<crate::database::FileMetadata>> {
+    let old_cache = ScanCache::load(cache_path)?;
+    let mut new_cache = ScanCache::default();
+
+    let entries = scan_dir_incremental(root, root, algorithm, &old_cache, &mut new_cache)?;
+
+    new_cache.save(cache_path)?;
+    Ok(entries)
+}
+
+fn scan_dir_incremental(
+    dir: &Path,
+    root: &Path,
+    algorithm: crate::file_processor::ChecksumAlgorithm,
+    old_cache: &ScanCache,
+    new_cache: &mut ScanCache,
+) -> Result<Vec<crate::database::FileMetadata>> {
+    use crate::database::FileMetadata;
+    use crate::file_processor::{sniff_mime_type, FileProcessor};
+
+    let relative_dir = dir.strip_prefix(root).unwrap_or(dir).to_path_buf();
+    let dir_stat = std::fs::metadata(dir).map_err(SanchayCoreError::Io)?;
+    let dir_modified_at = system_time_to_unix_seconds(dir_stat.modified().map_err(SanchayCoreError::Io)?);
+
+    let cached_children: HashMap<String, &CachedFileEntry> = old_cache
+        .dirs
+        .get(&relative_dir)
+        .filter(|cached| cached.dir_modified_at == dir_modified_at)
+        .map(|cached| cached.children.iter().map(|c| (c.file_name.clone(), c)).collect())
+        .unwrap_or_default();
+
+    let mut entries = Vec::new();
+    let mut children_cache = Vec::new();
+
+    for entry in std::fs::read_dir(dir).map_err(SanchayCoreError::Io)? {
+        let entry = entry.map_err(SanchayCoreError::Io)?;
+        let path = entry.path();
+        let file_type = entry.file_type().map_err(SanchayCoreError::Io)?;
+
+        if file_type.is_dir() {
+            entries.extend(scan_dir_incremental(&path, root, algorithm, old_cache, new_cache)?);
+            continue;
+        }
+        if !file_type.is_file() {
+            continue;
+        }
+
+        let file_name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        let stat = entry.metadata().map_err(SanchayCoreError::Io)?;
+        let size = stat.len();
+        let modified_at = TruncatedTimestamp::capture(stat.modified().map_err(SanchayCoreError::Io)?);
+        let created_at = stat
+            .created()
+            .map(system_time_to_unix_seconds)
+            .unwrap_or(modified_at.seconds);
+        let mime_type = sniff_mime_type(&path).map_err(SanchayCoreError::Io)?;
+
+        let checksum = match cached_children.get(&file_name) {
+            Some(cached) if cached.size == size && cached.modified_at == modified_at => cached.checksum.clone(),
+            _ => FileProcessor::new(path.clone()).compute_blake3_hash().map_err(SanchayCoreError::Io)?,
+        };
+
+        children_cache.push(CachedFileEntry {
+            file_name: file_name.clone(),
+            size,
+            modified_at,
+            checksum: checksum.clone(),
+        });
+        entries.push(FileMetadata {
+            path,
+            file_name,
+            size,
+            checksum,
+            modified_at: modified_at.seconds,
+            created_at,
+            mime_type,
+        });
+    }
+
+    new_cache.dirs.insert(relative_dir, CachedDirEntry { dir_modified_at, children: children_cache });
+
+    Ok(entries)
+}
+
+/// Mirrors `file_processor::system_time_to_unix_seconds`/`incremental::system_time_to_unix_seconds`;
+/// duplicated here rather than shared to avoid a cross-module dependency for one three-line helper.
+fn system_time_to_unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file_processor::ChecksumAlgorithm;
     use std::fs;
     use std::io::Write;
     use tempfile::tempdir; // A crate for creating temporary directories
@@ -100,8 +620,9 @@ mod tests {
     #[test]
     fn test_walk_empty_directory() {
         let tmp_dir = tempdir().unwrap();
-        let files = walk_directory_parallel(tmp_dir.path()).unwrap();
-        assert!(files.is_empty());
+        let outcome = walk_directory_parallel(tmp_dir.path(), None).unwrap();
+        assert!(outcome.files.is_empty());
+        assert!(outcome.warnings.is_empty());
     }
 
     #[test]
@@ -110,10 +631,10 @@ mod tests {
         fs::write(tmp_dir.path().join("file1.txt"), "content1").unwrap();
         fs::write(tmp_dir.path().join("file2.txt"), "content2").unwrap();
 
-        let files = walk_directory_parallel(tmp_dir.path()).unwrap();
-        assert_eq!(files.len(), 2);
-        assert!(files.iter().any(|p| p.file_name().unwrap() == "file1.txt"));
-        assert!(files.iter().any(|p| p.file_name().unwrap() == "file2.txt"));
+        let outcome = walk_directory_parallel(tmp_dir.path(), None).unwrap();
+        assert_eq!(outcome.files.len(), 2);
+        assert!(outcome.files.iter().any(|p| p.file_name().unwrap() == "file1.txt"));
+        assert!(outcome.files.iter().any(|p| p.file_name().unwrap() == "file2.txt"));
     }
 
     #[test]
@@ -127,18 +648,18 @@ mod tests {
         fs::create_dir(&sub_sub_dir).unwrap();
         fs::write(sub_sub_dir.join("file3.txt"), "content3").unwrap();
 
-        let files = walk_directory_parallel(tmp_dir.path()).unwrap();
-        assert_eq!(files.len(), 3);
-        assert!(files.iter().any(|p| p.file_name().unwrap() == "file1.txt"));
-        assert!(files.iter().any(|p| p.file_name().unwrap() == "file2.txt"));
-        assert!(files.iter().any(|p| p.file_name().unwrap() == "file3.txt"));
+        let outcome = walk_directory_parallel(tmp_dir.path(), None).unwrap();
+        assert_eq!(outcome.files.len(), 3);
+        assert!(outcome.files.iter().any(|p| p.file_name().unwrap() == "file1.txt"));
+        assert!(outcome.files.iter().any(|p| p.file_name().unwrap() == "file2.txt"));
+        assert!(outcome.files.iter().any(|p| p.file_name().unwrap() == "file3.txt"));
     }
 
     #[test]
     fn test_walk_non_existent_path() {
         let non_existent_path = PathBuf::from("non_existent_dir_12345");
-        let err = walk_directory_parallel(&non_existent_path).unwrap_err();
-        assert!(matches!(err, SanchayCoreError::IOError(ref io_err) if io_err.kind() == std::io::ErrorKind::NotFound));
+        let err = walk_directory_parallel(&non_existent_path, None).unwrap_err();
+        assert!(matches!(err, SanchayCoreError::Io(ref io_err) if io_err.kind() == std::io::ErrorKind::NotFound));
     }
 
     #[test]
@@ -147,9 +668,10 @@ mod tests {
         let file_path = tmp_dir.path().join("single_file.txt");
         fs::write(&file_path, "single content").unwrap();
 
-        let files = walk_directory_parallel(&file_path).unwrap();
-        assert_eq!(files.len(), 1);
-        assert_eq!(files[0], file_path);
+        let outcome = walk_directory_parallel(&file_path, None).unwrap();
+        assert_eq!(outcome.files.len(), 1);
+        assert_eq!(outcome.files[0], file_path);
+        assert!(outcome.warnings.is_empty());
     }
 
     #[test]
@@ -167,7 +689,7 @@ mod tests {
             fs::set_permissions(&protected_dir, perms).unwrap();
         }
 
-        let files = walk_directory_parallel(tmp_dir.path()).unwrap();
+        let outcome = walk_directory_parallel(tmp_dir.path(), None).unwrap();
 
         #[cfg(unix)]
         {
@@ -178,8 +700,243 @@ mod tests {
             fs::set_permissions(&protected_dir, perms).unwrap();
         }
 
-        // The file inside `protected_dir` should not be included if permissions prevented access.
-        // Other files in `tmp_dir` (if any were created for the test) would still be found.
-        assert_eq!(files.len(), 0, "No files should be collected from unreadable directory");
+        // The file inside `protected_dir` should not be included if permissions prevented access,
+        // and the denial should show up as a recorded warning rather than being swallowed.
+        assert_eq!(outcome.files.len(), 0, "No files should be collected from unreadable directory");
+        #[cfg(unix)]
+        {
+            assert_eq!(outcome.warnings.len(), 1);
+            assert_eq!(outcome.warnings[0].kind, std::io::ErrorKind::PermissionDenied);
+            assert!(outcome.warnings[0].path.ends_with("protected_dir"));
+        }
+    }
+
+    /// An in-memory [`Vfs`] backed by a few `HashMap`s instead of the real filesystem, so traversal
+    /// and permission-denial handling can be exercised without `tempfile` or real `chmod` calls.
+    #[derive(Default)]
+    struct MockVfs {
+        dirs: HashMap<PathBuf, Vec<PathBuf>>,
+        files: std::collections::HashSet<PathBuf>,
+        denied: std::collections::HashSet<PathBuf>,
+    }
+
+    impl MockVfs {
+        fn dir(mut self, path: &str, children: &[&str]) -> Self {
+            self.dirs.insert(
+                PathBuf::from(path),
+                children.iter().map(|c| PathBuf::from(format!("{path}/{c}"))).collect(),
+            );
+            self
+        }
+
+        fn file(mut self, path: &str) -> Self {
+            self.files.insert(PathBuf::from(path));
+            self
+        }
+
+        fn deny(mut self, path: &str) -> Self {
+            self.denied.insert(PathBuf::from(path));
+            self
+        }
+    }
+
+    impl Vfs for MockVfs {
+        fn canonicalize(&self, path: &Path) -> std::io::Result<PathBuf> {
+            Ok(path.to_path_buf())
+        }
+
+        fn metadata(&self, path: &Path) -> std::io::Result<VfsMetadata> {
+            self.symlink_metadata(path)
+        }
+
+        fn symlink_metadata(&self, path: &Path) -> std::io::Result<VfsMetadata> {
+            if self.dirs.contains_key(path) {
+                Ok(VfsMetadata::Directory)
+            } else if self.files.contains(path) {
+                Ok(VfsMetadata::File)
+            } else {
+                Err(std::io::Error::new(std::io::ErrorKind::NotFound, "mock path not found"))
+            }
+        }
+
+        fn read_dir(&self, path: &Path) -> std::io::Result<Vec<PathBuf>> {
+            if self.denied.contains(path) {
+                return Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "mock permission denied"));
+            }
+            self.dirs
+                .get(path)
+                .cloned()
+                .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "mock directory not found"))
+        }
+    }
+
+    #[test]
+    fn test_walk_with_vfs_finds_nested_files_without_touching_real_filesystem() {
+        let vfs = MockVfs::default()
+            .dir("/root", &["a.txt", "sub"])
+            .dir("/root/sub", &["b.txt"])
+            .file("/root/a.txt")
+            .file("/root/sub/b.txt");
+
+        let outcome = walk_directory_parallel_with_vfs(Path::new("/root"), None, &vfs).unwrap();
+        assert_eq!(outcome.files.len(), 2);
+        assert!(outcome.files.iter().any(|p| p.ends_with("a.txt")));
+        assert!(outcome.files.iter().any(|p| p.ends_with("b.txt")));
+        assert!(outcome.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_walk_with_vfs_records_warning_for_denied_directory() {
+        let vfs = MockVfs::default()
+            .dir("/root", &["a.txt", "locked"])
+            .file("/root/a.txt")
+            .dir("/root/locked", &["secret.txt"])
+            .file("/root/locked/secret.txt")
+            .deny("/root/locked");
+
+        let outcome = walk_directory_parallel_with_vfs(Path::new("/root"), None, &vfs).unwrap();
+        assert_eq!(outcome.files.len(), 1);
+        assert!(outcome.files[0].ends_with("a.txt"));
+        assert_eq!(outcome.warnings.len(), 1);
+        assert_eq!(outcome.warnings[0].kind, std::io::ErrorKind::PermissionDenied);
+        assert!(outcome.warnings[0].path.ends_with("locked"));
+    }
+
+    #[test]
+    fn test_walk_with_glob_include_matcher() {
+        let tmp_dir = tempdir().unwrap();
+        fs::write(tmp_dir.path().join("keep.rs"), "content").unwrap();
+        fs::write(tmp_dir.path().join("skip.txt"), "content").unwrap();
+
+        let matcher = GlobMatcher::new(&["*.rs".to_string()]).unwrap();
+        let outcome = walk_directory_parallel(tmp_dir.path(), Some(&matcher)).unwrap();
+        assert_eq!(outcome.files.len(), 1);
+        assert!(outcome.files[0].ends_with("keep.rs"));
+    }
+
+    #[test]
+    fn test_walk_with_difference_matcher_excludes_tests() {
+        let tmp_dir = tempdir().unwrap();
+        fs::create_dir(tmp_dir.path().join("src")).unwrap();
+        fs::create_dir(tmp_dir.path().join("src/tests")).unwrap();
+        fs::write(tmp_dir.path().join("src/lib.rs"), "content").unwrap();
+        fs::write(tmp_dir.path().join("src/tests/it.rs"), "content").unwrap();
+
+        let included = GlobMatcher::new(&["src/**/*.rs".to_string()]).unwrap();
+        let excluded = GlobMatcher::new(&["src/tests/**".to_string()]).unwrap();
+        let matcher = DifferenceMatcher::new(Box::new(included), Box::new(excluded));
+
+        let outcome = walk_directory_parallel(tmp_dir.path(), Some(&matcher)).unwrap();
+        assert_eq!(outcome.files.len(), 1);
+        assert!(outcome.files[0].ends_with("src/lib.rs"));
+    }
+
+    #[test]
+    fn test_intersection_matcher_requires_both() {
+        let a = GlobMatcher::new(&["*.rs".to_string()]).unwrap();
+        let b = GlobMatcher::new(&["lib.*".to_string()]).unwrap();
+        let matcher = IntersectionMatcher::new(Box::new(a), Box::new(b));
+
+        assert!(matcher.matches(Path::new("lib.rs")));
+        assert!(!matcher.matches(Path::new("main.rs")));
+    }
+
+    #[test]
+    fn test_walk_directory_classified_separates_files_from_directories() {
+        let tmp_dir = tempdir().unwrap();
+        fs::write(tmp_dir.path().join("file1.txt"), "content").unwrap();
+        fs::create_dir(tmp_dir.path().join("sub_dir")).unwrap();
+
+        let (files, rejected) = walk_directory_classified(tmp_dir.path()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("file1.txt"));
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].1, BadType::Directory);
+        assert_eq!(rejected[0].1.as_str(), "directory");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_directory_classified_reports_fifo() {
+        // `libc` must be declared as a dev-dependency in `crates/sanchay_core/Cargo.toml` for
+        // `mkfifo` below; it's only needed to set up this one test fixture.
+        use std::ffi::CString;
+
+        let tmp_dir = tempdir().unwrap();
+        let fifo_path = tmp_dir.path().join("a_fifo");
+        let c_path = CString::new(fifo_path.to_str().unwrap()).unwrap();
+        let ret = unsafe { libc::mkfifo(c_path.as_ptr(), 0o644) };
+        assert_eq!(ret, 0, "mkfifo should succeed");
+
+        let (files, rejected) = walk_directory_classified(tmp_dir.path()).unwrap();
+        assert!(files.is_empty());
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].1, BadType::Fifo);
+    }
+
+    #[test]
+    fn test_truncated_timestamp_equal_when_unambiguous_and_matching() {
+        let a = TruncatedTimestamp { seconds: 100, nanos: 0, second_ambiguous: false };
+        let b = TruncatedTimestamp { seconds: 100, nanos: 0, second_ambiguous: false };
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_truncated_timestamp_ambiguous_never_equal() {
+        let a = TruncatedTimestamp { seconds: 100, nanos: 0, second_ambiguous: true };
+        let b = TruncatedTimestamp { seconds: 100, nanos: 0, second_ambiguous: true };
+        assert_ne!(a, b);
+
+        let c = TruncatedTimestamp { seconds: 100, nanos: 0, second_ambiguous: false };
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_truncated_timestamp_capture_flags_current_second_as_ambiguous() {
+        let captured = TruncatedTimestamp::capture(SystemTime::now());
+        assert!(captured.second_ambiguous);
+    }
+
+    #[test]
+    fn test_scan_cache_load_missing_file_returns_empty() {
+        let tmp_dir = tempdir().unwrap();
+        let cache_path = tmp_dir.path().join("does_not_exist.json");
+        let cache = ScanCache::load(&cache_path).unwrap();
+        assert!(cache.dirs.is_empty());
+    }
+
+    #[test]
+    fn test_scan_directory_incremental_returns_entries_for_all_files() {
+        let tmp_dir = tempdir().unwrap();
+        let cache_path = tmp_dir.path().join("cache.json");
+        fs::write(tmp_dir.path().join("a.txt"), b"alpha").unwrap();
+        let sub_dir = tmp_dir.path().join("sub");
+        fs::create_dir(&sub_dir).unwrap();
+        fs::write(sub_dir.join("b.txt"), b"bravo").unwrap();
+
+        let entries = scan_directory_incremental(tmp_dir.path(), &cache_path, ChecksumAlgorithm::SHA256).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(cache_path.exists());
+    }
+
+    #[test]
+    fn test_scan_directory_incremental_detects_content_change_after_cache_write() {
+        let tmp_dir = tempdir().unwrap();
+        let cache_path = tmp_dir.path().join("cache.json");
+        let file_path = tmp_dir.path().join("a.txt");
+        fs::write(&file_path, b"before").unwrap();
+
+        let first = scan_directory_incremental(tmp_dir.path(), &cache_path, ChecksumAlgorithm::SHA256).unwrap();
+        let first_checksum = first.iter().find(|e| e.path == file_path).unwrap().checksum.clone();
+
+        // Ensure the new mtime is observably different from the first write so the cache can't
+        // mistake the edit for an unchanged file.
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        fs::write(&file_path, b"after, much longer than before").unwrap();
+
+        let second = scan_directory_incremental(tmp_dir.path(), &cache_path, ChecksumAlgorithm::SHA256).unwrap();
+        let second_checksum = second.iter().find(|e| e.path == file_path).unwrap().checksum.clone();
+
+        assert_ne!(first_checksum, second_checksum);
     }
 }
\ No newline at end of file
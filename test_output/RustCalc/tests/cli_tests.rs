@@ -168,3 +168,40 @@ fn test_interactive_mode_error_handling() {
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(stderr.len() > 0);
 }
+
+// `-i` against a piped (non-tty) stdin, exercised by every test above, always takes
+// `run_raw_loop`. These tests instead attach the process to a real pseudo-terminal via
+// `rexpect`, so `-i` takes `run_repl` and `colorize_result`'s tty branch.
+
+#[test]
+fn test_repl_mode_colorizes_result_on_a_tty() {
+    let mut session = rexpect::spawn("target/debug/RustCalc -i", Some(2_000)).expect("Failed to spawn process on a pty");
+    session.exp_string("> ").expect("Failed to see the prompt");
+
+    session.send_line("5 + 5").expect("Failed to send expression");
+    // `colorize_result` only wraps the result in the ANSI green escape when stdout is a tty;
+    // the piped-mode tests above never see this since their stdout is a pipe.
+    let seen = session.exp_string("\u{1b}[32m10\u{1b}[0m").expect("Expected the result to be colorized green");
+    assert!(seen.contains("10"));
+
+    session.send_line("exit").expect("Failed to send exit");
+    session.exp_eof().expect("Expected the process to exit after 'exit'");
+}
+
+#[test]
+fn test_repl_mode_ctrl_c_cancels_line_without_exiting() {
+    let mut session = rexpect::spawn("target/debug/RustCalc -i", Some(2_000)).expect("Failed to spawn process on a pty");
+    session.exp_string("> ").expect("Failed to see the first prompt");
+
+    // Partially type an expression, then Ctrl-C: `run_repl` should cancel just that line and
+    // print a fresh prompt rather than exiting; `run_raw_loop` exits only on `exit`/`quit`.
+    session.send("2 +").expect("Failed to send partial input");
+    session.send_control('c').expect("Failed to send Ctrl-C");
+    session.exp_string("> ").expect("Expected a fresh prompt after Ctrl-C, not an exit");
+
+    session.send_line("3 + 3").expect("Failed to send expression");
+    session.exp_string("6").expect("Expected the REPL to still be evaluating after the cancelled line");
+
+    session.send_control('d').expect("Failed to send Ctrl-D");
+    session.exp_eof().expect("Expected Ctrl-D to exit the REPL");
+}